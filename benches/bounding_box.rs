@@ -10,6 +10,7 @@ fn main() {
     transform_iso();
     transform_affine3();
     transform_conformal3();
+    intersect_ray();
 }
 
 bench_binop_ref!(
@@ -43,3 +44,11 @@ bench_binop_ref!(
     from1 => random_bounding_box,
     from2 => random_conformal3
 );
+
+bench_binop_ref!(
+    intersect_ray,
+    "bbox intersected with ray",
+    op => intersect_ray,
+    from1 => random_bounding_box,
+    from2 => random_ray3
+);
@@ -7,6 +7,7 @@ use macaw::BoundingBox;
 use macaw::Conformal3;
 use macaw::IsoTransform;
 use macaw::Quat;
+use macaw::Ray3;
 use macaw::Vec2;
 use macaw::Vec3;
 use macaw::Vec3A;
@@ -120,3 +121,7 @@ pub fn random_conformal3(rng: &mut PCG32) -> Conformal3 {
 pub fn random_bounding_box(rng: &mut PCG32) -> BoundingBox {
     BoundingBox::from_center_size(random_vec3(rng), random_vec3(rng))
 }
+
+pub fn random_ray3(rng: &mut PCG32) -> Ray3 {
+    Ray3::from_origin_dir(random_vec3(rng), random_nonzero_vec3(rng).normalize())
+}
@@ -1,4 +1,6 @@
 use super::Mat3A;
+use super::Plane3;
+use super::Ray3;
 use super::Vec3;
 
 /// A 3-dimensional axis-aligned bounding box
@@ -20,6 +22,28 @@ impl core::fmt::Debug for BoundingBox {
     }
 }
 
+/// The result of [`BoundingBox::sweep`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SweepHit {
+    /// Time of impact in `[0, 1]`, as a fraction of the velocities.
+    ///
+    /// Zero if the boxes were already overlapping.
+    pub time: f32,
+
+    /// The contact normal, pointing from the other box towards this box.
+    ///
+    /// Always along one of the coordinate axes.
+    pub normal: Vec3,
+
+    /// The axis of the contact normal (0, 1 or 2 for x, y or z).
+    pub axis: usize,
+
+    /// How far this box needs to move along `normal` to separate from the other box.
+    ///
+    /// Zero unless the boxes were already overlapping.
+    pub penetration: f32,
+}
+
 #[allow(unused)]
 impl BoundingBox {
     /// A [`BoundingBox`] that only contains [`Vec3::ZERO`].
@@ -100,6 +124,12 @@ impl BoundingBox {
         s.x * s.y * s.z
     }
 
+    /// Only correct for positively sized boxes.
+    pub fn surface_area(&self) -> f32 {
+        let s = self.size();
+        2.0 * (s.x * s.y + s.y * s.z + s.z * s.x)
+    }
+
     /// True if and only if there is at least one point for which `bb.contains(point)` is true.
     ///
     /// Will return `true` if [`Self::min`] == [`Self::max`].
@@ -201,6 +231,77 @@ impl BoundingBox {
         ]
     }
 
+    /// Split the box into eight equally sized octants around its center.
+    ///
+    /// Uses the same order as [`Self::corners`], i.e. octant `i` contains corner `i`.
+    #[cfg(not(target_arch = "spirv"))]
+    pub fn octants(&self) -> [Self; 8] {
+        let center = self.center();
+        self.corners()
+            .map(|corner| Self::from_min_max(corner.min(center), corner.max(center)))
+    }
+
+    /// Split the box in two at `value` along `axis` (0, 1 or 2 for x, y or z).
+    ///
+    /// Returns the parts below and above `value`.
+    /// If `value` is outside the box, one of the parts is [`Self::nothing`].
+    ///
+    /// # Panics
+    ///
+    /// If `axis` is larger than 2.
+    pub fn split_at(&self, axis: usize, value: f32) -> (Self, Self) {
+        let mut below = *self;
+        let mut above = *self;
+        below.max[axis] = value.min(self.max[axis]);
+        above.min[axis] = value.max(self.min[axis]);
+        let or_nothing = |bb: Self| if bb.is_nothing() { Self::nothing() } else { bb };
+        (or_nothing(below), or_nothing(above))
+    }
+
+    /// Split the box in two by a plane.
+    ///
+    /// Returns the tight bounding boxes of the parts behind and in front of the plane,
+    /// where the front is the side the plane normal points towards.
+    /// If the box is entirely on one side, the other part is [`Self::nothing`].
+    #[cfg(not(target_arch = "spirv"))]
+    pub fn split_by_plane(&self, plane: &Plane3) -> (Self, Self) {
+        if self.is_nothing() {
+            return (Self::nothing(), Self::nothing());
+        }
+
+        let corners = self.corners();
+        let dist = corners.map(|p| plane.distance(p));
+
+        let mut behind = Self::nothing();
+        let mut in_front = Self::nothing();
+        for (p, dist) in corners.into_iter().zip(dist) {
+            if dist <= 0.0 {
+                behind.extend(p);
+            }
+            if 0.0 <= dist {
+                in_front.extend(p);
+            }
+        }
+
+        for_each_edge_crossing(&dist, |a, b, t| {
+            let p = corners[a].lerp(corners[b], t);
+            behind.extend(p);
+            in_front.extend(p);
+        });
+
+        (behind, in_front)
+    }
+
+    /// The tight bounding box of the part of this box in front of the plane,
+    /// i.e. on the side the plane normal points towards.
+    ///
+    /// Returns [`Self::nothing`] if the box is entirely behind the plane.
+    #[cfg(not(target_arch = "spirv"))]
+    #[must_use]
+    pub fn clipped_by_plane(&self, plane: &Plane3) -> Self {
+        self.split_by_plane(plane).1
+    }
+
     /// Enlarge the box to include this point.
     #[inline]
     pub fn extend(&mut self, pos: Vec3) {
@@ -238,6 +339,285 @@ impl BoundingBox {
             && (self.min.z <= point.z && point.z <= self.max.z)
     }
 
+    /// Intersects the box with a ray using the slab test.
+    ///
+    /// Returns the distances `(t_enter, t_exit)` along the ray where it enters and exits the box,
+    /// or [`None`] if the ray misses the box or the box is behind the ray.
+    /// `t_enter` is negative if the ray starts inside the box.
+    ///
+    /// The ray direction does not need to be normalized, but if it is, the returned
+    /// values correspond to world space distances.
+    #[cfg(not(target_arch = "spirv"))] // TODO: large Options in rust-gpu
+    #[inline]
+    pub fn intersect_ray(&self, ray: &Ray3) -> Option<(f32, f32)> {
+        self.intersect_ray_inv_dir(ray.origin, ray.inv_dir())
+    }
+
+    /// Same as [`Self::intersect_ray`], but takes the precomputed reciprocal of the
+    /// ray direction (see [`Ray3::inv_dir`]).
+    ///
+    /// Useful when testing the same ray against many boxes.
+    ///
+    /// Axis-parallel rays (where a component of `inv_dir` is infinite) are handled
+    /// by checking if the origin is within the slab of that axis.
+    #[cfg(not(target_arch = "spirv"))] // TODO: large Options in rust-gpu
+    #[inline]
+    pub fn intersect_ray_inv_dir(&self, origin: Vec3, inv_dir: Vec3) -> Option<(f32, f32)> {
+        let ((t_enter, _), (t_exit, _)) = self.clip_ray_inv_dir(origin, inv_dir)?;
+        Some((t_enter, t_exit))
+    }
+
+    /// Like [`Self::intersect_ray_inv_dir`], but also returns the axes of the slabs
+    /// where the ray enters and exits the box.
+    ///
+    /// The axis is 0 if the ray never enters or exits, i.e. if the distance is infinite.
+    #[cfg(not(target_arch = "spirv"))] // TODO: large Options in rust-gpu
+    pub(crate) fn clip_ray_inv_dir(
+        &self,
+        origin: Vec3,
+        inv_dir: Vec3,
+    ) -> Option<((f32, usize), (f32, usize))> {
+        if self.is_nothing() {
+            return None;
+        }
+
+        let mut enter = (f32::NEG_INFINITY, 0);
+        let mut exit = (f32::INFINITY, 0);
+
+        for i in 0..3 {
+            if inv_dir[i].is_infinite() {
+                // Parallel to the slab, so we never enter or exit it.
+                if origin[i] < self.min[i] || self.max[i] < origin[i] {
+                    return None;
+                }
+            } else {
+                let t0 = (self.min[i] - origin[i]) * inv_dir[i];
+                let t1 = (self.max[i] - origin[i]) * inv_dir[i];
+                if enter.0 < t0.min(t1) {
+                    enter = (t0.min(t1), i);
+                }
+                if t0.max(t1) < exit.0 {
+                    exit = (t0.max(t1), i);
+                }
+            }
+        }
+
+        (enter.0 <= exit.0 && 0.0 <= exit.0).then_some((enter, exit))
+    }
+
+    /// Returns `true` if the boxes overlap (or touch).
+    ///
+    /// Equivalent to `self.intersection(*other).is_something()`, but faster.
+    #[must_use]
+    pub fn intersects(&self, other: &Self) -> bool {
+        (self.min.x <= other.max.x && other.min.x <= self.max.x)
+            && (self.min.y <= other.max.y && other.min.y <= self.max.y)
+            && (self.min.z <= other.max.z && other.min.z <= self.max.z)
+    }
+
+    /// Returns the point within the box that is closest to `point`.
+    ///
+    /// Points inside the box are returned unchanged.
+    /// Requires a well-formed box for the result to be valid.
+    #[inline]
+    pub fn closest_point(&self, point: Vec3) -> Vec3 {
+        point.max(self.min).min(self.max)
+    }
+
+    /// The squared distance from the box to `point`, or zero if the point is inside the box.
+    #[inline]
+    pub fn distance_squared(&self, point: Vec3) -> f32 {
+        self.closest_point(point).distance_squared(point)
+    }
+
+    /// The distance from the box to `point`, or zero if the point is inside the box.
+    #[inline]
+    pub fn distance(&self, point: Vec3) -> f32 {
+        self.distance_squared(point).sqrt()
+    }
+
+    /// The signed distance from the surface of the box to `point`.
+    ///
+    /// Negative when the point is inside the box.
+    pub fn signed_distance(&self, point: Vec3) -> f32 {
+        let q = (point - self.center()).abs() - self.half_size();
+        let outside = q.max(Vec3::ZERO).length();
+        let inside = q.max_element().min(0.0);
+        outside + inside
+    }
+
+    /// The distance between the closest points of the two boxes, or zero if they overlap.
+    pub fn distance_to_bounding_box(&self, other: &Self) -> f32 {
+        let gap = (other.min - self.max).max(self.min - other.max);
+        gap.max(Vec3::ZERO).length()
+    }
+
+    /// The signed distance from the plane to the closest point of the box,
+    /// or zero if the box intersects the plane.
+    ///
+    /// Positive if the box is in front of the plane (in the direction of the normal)
+    /// and negative if it is behind it.
+    ///
+    /// The plane must be normalized.
+    pub fn distance_to_plane(&self, plane: &Plane3) -> f32 {
+        let center_distance = plane.distance(self.center());
+        let radius = self.half_size().dot(plane.normal.abs());
+        if center_distance.abs() <= radius {
+            0.0
+        } else {
+            center_distance - radius.copysign(center_distance)
+        }
+    }
+
+    /// The distance between the box and the closest point on the ray, or zero if they intersect.
+    ///
+    /// Only the part of the ray in front of its origin is considered.
+    /// The ray direction does not need to be normalized.
+    #[cfg(not(target_arch = "spirv"))]
+    pub fn distance_to_ray(&self, ray: &Ray3) -> f32 {
+        // The squared distance to the box along the ray is a convex, piecewise quadratic function
+        // of `t`, with pieces separated where the ray crosses the planes of the box.
+        // Find the minimum of each piece and keep the smallest.
+        let mut breaks = [f32::INFINITY; 7];
+        breaks[0] = 0.0;
+        for i in 0..3 {
+            if ray.dir[i] != 0.0 {
+                breaks[1 + 2 * i] = (self.min[i] - ray.origin[i]) / ray.dir[i];
+                breaks[2 + 2 * i] = (self.max[i] - ray.origin[i]) / ray.dir[i];
+            }
+        }
+        for t in &mut breaks {
+            *t = t.max(0.0);
+        }
+        breaks.sort_unstable_by(f32::total_cmp);
+
+        let mut min_distance_squared = f32::INFINITY;
+        for piece in 0..breaks.len() {
+            let start = breaks[piece];
+            let end = breaks.get(piece + 1).copied().unwrap_or(f32::INFINITY);
+            let mid = if end.is_finite() {
+                0.5 * (start + end)
+            } else {
+                start + 1.0
+            };
+
+            // Within this piece, each axis is either within the slab of the box or clamped
+            // against one of its sides.
+            let mid_point = ray.point_along(mid);
+            let side = self.closest_point(mid_point);
+            let (mut num, mut denom) = (0.0, 0.0);
+            for i in 0..3 {
+                if side[i] != mid_point[i] {
+                    num -= ray.dir[i] * (ray.origin[i] - side[i]);
+                    denom += ray.dir[i] * ray.dir[i];
+                }
+            }
+            let t = if denom > 0.0 {
+                (num / denom).clamp(start, end)
+            } else {
+                start
+            };
+
+            min_distance_squared =
+                min_distance_squared.min(self.distance_squared(ray.point_along(t)));
+        }
+
+        min_distance_squared.sqrt()
+    }
+
+    /// Continuous collision between two moving boxes.
+    ///
+    /// `velocity` and `other_velocity` are the displacements of the boxes over the time step,
+    /// so the boxes are at `self.translated(t * velocity)` and
+    /// `other.translated(t * other_velocity)` at time `t` in `[0, 1]`.
+    ///
+    /// Returns the first time the boxes touch while moving towards each other,
+    /// or [`None`] if they do not collide during the time step.
+    /// Boxes that merely touch or slide along each other do not collide.
+    /// If the boxes already overlap at `t = 0`, the hit describes how to separate them.
+    #[cfg(not(target_arch = "spirv"))] // TODO: large Options in rust-gpu
+    pub fn sweep(&self, velocity: Vec3, other: &Self, other_velocity: Vec3) -> Option<SweepHit> {
+        if self.is_nothing() || other.is_nothing() {
+            return None;
+        }
+
+        // Move `self` relative to a static `other`.
+        let v = velocity - other_velocity;
+        let mut t_enter = f32::NEG_INFINITY;
+        let mut t_exit = f32::INFINITY;
+        let mut enter_axis = 0;
+
+        for i in 0..3 {
+            let (t0, t1) = if v[i] == 0.0 {
+                if self.max[i] <= other.min[i] || other.max[i] <= self.min[i] {
+                    return None; // Separated along an axis we don't move along.
+                }
+                continue;
+            } else if v[i] > 0.0 {
+                (
+                    (other.min[i] - self.max[i]) / v[i],
+                    (other.max[i] - self.min[i]) / v[i],
+                )
+            } else {
+                (
+                    (other.max[i] - self.min[i]) / v[i],
+                    (other.min[i] - self.max[i]) / v[i],
+                )
+            };
+
+            if t0 > t_enter {
+                t_enter = t0;
+                enter_axis = i;
+            }
+            t_exit = t_exit.min(t1);
+        }
+
+        if t_exit <= t_enter || t_exit <= 0.0 || 1.0 < t_enter {
+            return None;
+        }
+
+        if 0.0 <= t_enter {
+            let mut normal = Vec3::ZERO;
+            normal[enter_axis] = -v[enter_axis].signum();
+            Some(SweepHit {
+                time: t_enter,
+                normal,
+                axis: enter_axis,
+                penetration: 0.0,
+            })
+        } else {
+            // Already overlapping, so find the axis of least penetration.
+            let below = self.max - other.min; // Separate by moving `self` in -axis.
+            let above = other.max - self.min; // Separate by moving `self` in +axis.
+            let depth = below.min(above);
+            let axis = depth.min_position();
+            let mut normal = Vec3::ZERO;
+            normal[axis] = if below[axis] < above[axis] { -1.0 } else { 1.0 };
+            Some(SweepHit {
+                time: 0.0,
+                normal,
+                axis,
+                penetration: depth[axis],
+            })
+        }
+    }
+
+    /// Continuous collision of a box moving against static boxes.
+    ///
+    /// Returns the index of the first obstacle hit during the time step, and the hit.
+    /// See [`Self::sweep`] for details.
+    #[cfg(not(target_arch = "spirv"))] // TODO: large Options in rust-gpu
+    pub fn sweep_static(&self, velocity: Vec3, obstacles: &[Self]) -> Option<(usize, SweepHit)> {
+        let mut first: Option<(usize, SweepHit)> = None;
+        for (index, obstacle) in obstacles.iter().enumerate() {
+            let hit = self.sweep(velocity, obstacle, Vec3::ZERO);
+            if let Some(hit) = hit.filter(|hit| first.is_none_or(|(_, f)| hit.time < f.time)) {
+                first = Some((index, hit));
+            }
+        }
+        first
+    }
+
     /// Expand with this much padding on each side.
     #[must_use]
     pub fn expanded(&self, padding: Vec3) -> Self {
@@ -311,6 +691,80 @@ impl BoundingBox {
             transform_bounding_box(self.half_size(), self.center(), m)
         }
     }
+
+    /// Project the box through a view-projection matrix with a `[0, 1]` depth range,
+    /// such as the ones created by [`crate::Mat4::perspective_rh`].
+    ///
+    /// Returns the bounds of the projected box in normalized device coordinates,
+    /// where `x` and `y` is the screen-space rectangle and `z` is the range of depths.
+    /// The part of the box between the camera and the near plane is clipped away first,
+    /// so the result is correct even when the box crosses the near plane.
+    /// Returns [`None`] if the box is entirely between the camera and the near plane
+    /// (nothing is visible).
+    ///
+    /// The result is not clamped to the `[-1, 1]` range of the screen.
+    #[cfg(not(target_arch = "spirv"))] // TODO: large Options in rust-gpu
+    pub fn projected_ndc_bounds(&self, view_projection: &crate::Mat4) -> Option<Self> {
+        // The near plane is at `z = 0` in clip space.
+        self.projected_ndc_bounds_clipped(view_projection, crate::Vec4::Z)
+    }
+
+    /// Like [`Self::projected_ndc_bounds`], but for a view-projection matrix using reverse-Z,
+    /// where the near plane maps to depth `1`,
+    /// such as the ones created by [`crate::Mat4::perspective_infinite_reverse_rh`].
+    #[cfg(not(target_arch = "spirv"))] // TODO: large Options in rust-gpu
+    pub fn projected_ndc_bounds_reverse_z(&self, view_projection: &crate::Mat4) -> Option<Self> {
+        // The near plane is at `z = w` in clip space.
+        self.projected_ndc_bounds_clipped(view_projection, crate::Vec4::new(0.0, 0.0, -1.0, 1.0))
+    }
+
+    /// Project the part of the box where `near_plane.dot(clip_pos) >= 0`.
+    #[cfg(not(target_arch = "spirv"))]
+    fn projected_ndc_bounds_clipped(
+        &self,
+        view_projection: &crate::Mat4,
+        near_plane: crate::Vec4,
+    ) -> Option<Self> {
+        if self.is_nothing() {
+            return None;
+        }
+
+        let clip = self
+            .corners()
+            .map(|corner| *view_projection * corner.extend(1.0));
+        let dist = clip.map(|p| near_plane.dot(p));
+
+        let mut result = Self::nothing();
+        for (p, dist) in clip.iter().zip(dist) {
+            if 0.0 <= dist {
+                result.extend(p.truncate() / p.w);
+            }
+        }
+
+        // Add the points where the edges cross the near plane.
+        for_each_edge_crossing(&dist, |a, b, t| {
+            let p = clip[a].lerp(clip[b], t);
+            result.extend(p.truncate() / p.w);
+        });
+
+        result.is_something().then_some(result)
+    }
+}
+
+/// Call `f(a, b, t)` for every edge between the corners `a` and `b` that crosses zero,
+/// where `t` is the crossing point along the edge.
+///
+/// Corner indices have x, y and z in bits 2, 1 and 0 (see [`BoundingBox::corners`]).
+#[cfg(not(target_arch = "spirv"))]
+fn for_each_edge_crossing(dist: &[f32; 8], mut f: impl FnMut(usize, usize, f32)) {
+    for axis_bit in [1, 2, 4] {
+        for a in (0..8).filter(|i| i & axis_bit == 0) {
+            let b = a | axis_bit;
+            if (dist[a] < 0.0) != (dist[b] < 0.0) {
+                f(a, b, dist[a] / (dist[a] - dist[b]));
+            }
+        }
+    }
 }
 
 trait TransformPoint3 {
@@ -451,6 +905,266 @@ mod test {
         );
     }
 
+    #[test]
+    fn test_intersect_ray() {
+        #![allow(clippy::float_cmp)]
+        let bb = BoundingBox::from_min_max(Vec3::splat(1.0), Vec3::splat(2.0));
+
+        let ray = Ray3::from_origin_dir(Vec3::splat(1.5) - 5.0 * Vec3::X, Vec3::X);
+        assert_eq!(bb.intersect_ray(&ray), Some((4.5, 5.5)));
+
+        // starting inside the box
+        let ray = Ray3::from_origin_dir(Vec3::splat(1.5), Vec3::X);
+        assert_eq!(bb.intersect_ray(&ray), Some((-0.5, 0.5)));
+
+        // box behind the ray
+        let ray = Ray3::from_origin_dir(Vec3::splat(1.5) + 5.0 * Vec3::X, Vec3::X);
+        assert_eq!(bb.intersect_ray(&ray), None);
+
+        // axis-parallel rays, both outside and exactly on the boundary of a slab
+        let ray = Ray3::from_origin_dir(Vec3::new(0.0, 3.0, 1.5), Vec3::X);
+        assert_eq!(bb.intersect_ray(&ray), None);
+        let ray = Ray3::from_origin_dir(Vec3::new(0.0, 2.0, 1.0), Vec3::X);
+        assert_eq!(bb.intersect_ray(&ray), Some((1.0, 2.0)));
+
+        // diagonal
+        let ray = Ray3::from_origin_dir(Vec3::ZERO, Vec3::ONE.normalize());
+        let (t_enter, t_exit) = bb.intersect_ray(&ray).unwrap();
+        assert!((t_enter - 3.0_f32.sqrt()).abs() < 1e-6);
+        assert!((t_exit - 2.0 * 3.0_f32.sqrt()).abs() < 1e-6);
+
+        // missing diagonal
+        let ray = Ray3::from_origin_dir(Vec3::ZERO, Vec3::new(1.0, -1.0, 1.0).normalize());
+        assert_eq!(bb.intersect_ray(&ray), None);
+
+        assert_eq!(BoundingBox::nothing().intersect_ray(&ray), None);
+        assert_eq!(
+            BoundingBox::everything().intersect_ray(&ray),
+            Some((f32::NEG_INFINITY, f32::INFINITY))
+        );
+    }
+
+    #[test]
+    fn test_distances() {
+        #![allow(clippy::float_cmp)]
+        let bb = BoundingBox::from_min_max(Vec3::ZERO, Vec3::new(2.0, 4.0, 6.0));
+
+        assert_eq!(
+            bb.closest_point(Vec3::new(-1.0, 2.0, 8.0)),
+            Vec3::new(0.0, 2.0, 6.0)
+        );
+        assert_eq!(bb.distance_squared(Vec3::new(-1.0, 2.0, 8.0)), 5.0);
+        assert_eq!(bb.distance(Vec3::new(1.0, 2.0, 3.0)), 0.0);
+
+        assert_eq!(bb.signed_distance(Vec3::new(1.0, 2.0, 3.0)), -1.0);
+        assert_eq!(bb.signed_distance(Vec3::new(1.0, 2.0, 7.0)), 1.0);
+        assert_eq!(bb.signed_distance(Vec3::new(5.0, 8.0, 3.0)), 5.0);
+
+        let other = BoundingBox::from_min_max(Vec3::new(5.0, 8.0, 0.0), Vec3::splat(10.0));
+        assert_eq!(bb.distance_to_bounding_box(&other), 5.0);
+        assert_eq!(other.distance_to_bounding_box(&bb), 5.0);
+        assert_eq!(bb.distance_to_bounding_box(&bb.translated(Vec3::ONE)), 0.0);
+
+        let plane = Plane3::from_normal_point(Vec3::X, Vec3::new(5.0, 0.0, 0.0));
+        assert_eq!(bb.distance_to_plane(&plane), -3.0);
+        let plane = Plane3::from_normal_point(-Vec3::X, Vec3::new(5.0, 0.0, 0.0));
+        assert_eq!(bb.distance_to_plane(&plane), 3.0);
+        let plane = Plane3::from_normal_point(Vec3::ONE.normalize(), Vec3::ONE);
+        assert_eq!(bb.distance_to_plane(&plane), 0.0);
+    }
+
+    #[test]
+    fn test_subdivision() {
+        let bb = BoundingBox::from_min_max(Vec3::ZERO, Vec3::new(2.0, 4.0, 6.0));
+
+        let octants = bb.octants();
+        for (octant, corner) in octants.iter().zip(bb.corners()) {
+            assert_eq!(octant.size(), 0.5 * bb.size());
+            assert!(octant.contains(corner));
+            assert!(octant.contains(bb.center()));
+        }
+
+        let (below, above) = bb.split_at(1, 1.0);
+        assert_eq!(
+            below,
+            BoundingBox::from_min_max(Vec3::ZERO, Vec3::new(2.0, 1.0, 6.0))
+        );
+        assert_eq!(
+            above,
+            BoundingBox::from_min_max(Vec3::new(0.0, 1.0, 0.0), bb.max)
+        );
+
+        let (below, above) = bb.split_at(2, 10.0);
+        assert_eq!(below, bb);
+        assert_eq!(above, BoundingBox::nothing());
+    }
+
+    #[test]
+    fn test_split_by_plane() {
+        let bb = BoundingBox::from_min_max(Vec3::ZERO, Vec3::splat(2.0));
+
+        // Axis aligned plane, same as `split_at`.
+        let plane = Plane3::from_normal_point(Vec3::X, Vec3::splat(0.5));
+        assert_eq!(bb.split_by_plane(&plane), bb.split_at(0, 0.5));
+
+        // Cuts off the corner at the origin.
+        let plane = Plane3::from_normal_point(Vec3::ONE.normalize(), Vec3::new(1.0, 0.0, 0.0));
+        let (behind, in_front) = bb.split_by_plane(&plane);
+        assert!(behind.min.abs_diff_eq(Vec3::ZERO, 1e-6));
+        assert!(behind.max.abs_diff_eq(Vec3::ONE, 1e-6));
+        assert_eq!(in_front, bb);
+        assert_eq!(bb.clipped_by_plane(&plane), in_front);
+
+        // Entirely on one side.
+        let plane = Plane3::from_normal_point(Vec3::Y, Vec3::splat(-1.0));
+        assert_eq!(bb.split_by_plane(&plane), (BoundingBox::nothing(), bb));
+        let plane = Plane3::from_normal_point(-Vec3::Y, Vec3::splat(-1.0));
+        assert_eq!(bb.split_by_plane(&plane), (bb, BoundingBox::nothing()));
+    }
+
+    #[test]
+    fn test_sweep() {
+        #![allow(clippy::float_cmp)]
+        let a = BoundingBox::from_min_max(Vec3::ZERO, Vec3::ONE);
+        let b = BoundingBox::from_min_max(Vec3::new(3.0, 0.5, 0.0), Vec3::new(4.0, 1.5, 1.0));
+
+        // Moving towards each other along x.
+        let hit = a
+            .sweep(Vec3::new(2.0, 0.0, 0.0), &b, Vec3::new(-2.0, 0.0, 0.0))
+            .unwrap();
+        assert_eq!(hit.time, 0.5);
+        assert_eq!(hit.normal, -Vec3::X);
+        assert_eq!(hit.axis, 0);
+        assert_eq!(hit.penetration, 0.0);
+        assert_eq!(
+            b.sweep(Vec3::new(-2.0, 0.0, 0.0), &a, Vec3::new(2.0, 0.0, 0.0))
+                .unwrap()
+                .normal,
+            Vec3::X
+        );
+
+        // Too slow, moving away, or missing.
+        assert_eq!(a.sweep(Vec3::new(1.0, 0.0, 0.0), &b, Vec3::ZERO), None);
+        assert_eq!(a.sweep(Vec3::new(-5.0, 0.0, 0.0), &b, Vec3::ZERO), None);
+        assert_eq!(
+            a.sweep(
+                Vec3::new(5.0, 0.0, 0.0),
+                &b.translated(Vec3::Z * 2.0),
+                Vec3::ZERO
+            ),
+            None
+        );
+
+        // Sliding along a touching face is not a collision.
+        let floor =
+            BoundingBox::from_min_max(Vec3::new(-10.0, -1.0, -10.0), Vec3::new(10.0, 0.0, 10.0));
+        assert_eq!(a.sweep(Vec3::new(5.0, 0.0, 0.0), &floor, Vec3::ZERO), None);
+        let hit = a
+            .sweep(Vec3::new(1.0, -1.0, 0.0), &floor, Vec3::ZERO)
+            .unwrap();
+        assert_eq!((hit.time, hit.normal), (0.0, Vec3::Y));
+
+        // Already overlapping.
+        let hit = a
+            .sweep(
+                Vec3::ZERO,
+                &a.translated(Vec3::new(0.0, 0.25, 0.0)),
+                Vec3::ZERO,
+            )
+            .unwrap();
+        assert_eq!(hit.time, 0.0);
+        assert_eq!(hit.normal, -Vec3::Y);
+        assert_eq!(hit.axis, 1);
+        assert_eq!(hit.penetration, 0.75);
+
+        let obstacles = [b.translated(Vec3::X * 2.0), b, floor];
+        let (index, hit) = a
+            .sweep_static(Vec3::new(4.0, 0.0, 0.0), &obstacles)
+            .unwrap();
+        assert_eq!(index, 1);
+        assert_eq!(hit.time, 0.5);
+        assert_eq!(a.sweep_static(Vec3::new(0.0, 4.0, 0.0), &obstacles), None);
+    }
+
+    #[test]
+    fn test_projected_ndc_bounds() {
+        use crate::Mat4;
+        use std::f32::consts::FRAC_PI_2;
+
+        let view = Mat4::look_at_rh(Vec3::new(0.0, 0.0, 2.0), Vec3::ZERO, Vec3::Y);
+        let projection = Mat4::perspective_rh(FRAC_PI_2, 1.0, 1.0, 100.0);
+        let view_projection = projection * view;
+
+        // In front of the camera, the projection of the corners.
+        let bb = BoundingBox::from_min_max(Vec3::new(-1.0, -1.0, -8.0), Vec3::new(1.0, 1.0, -3.0));
+        let expected = BoundingBox::from_points(
+            bb.corners()
+                .into_iter()
+                .map(|p| view_projection.project_point3(p)),
+        );
+        let ndc = bb.projected_ndc_bounds(&view_projection).unwrap();
+        assert!(ndc.min.abs_diff_eq(expected.min, 1e-6));
+        assert!(ndc.max.abs_diff_eq(expected.max, 1e-6));
+        assert!(ndc.min.abs_diff_eq(Vec3::new(-0.2, -0.2, ndc.min.z), 1e-6));
+
+        // Crossing the near plane at view distance 1, where x and y span [-1, 1].
+        let bb = BoundingBox::from_min_max(Vec3::new(-1.0, -1.0, -8.0), Vec3::new(1.0, 1.0, 5.0));
+        let far_depth = view_projection.project_point3(Vec3::new(0.0, 0.0, -8.0)).z;
+        let ndc = bb.projected_ndc_bounds(&view_projection).unwrap();
+        assert!(ndc.min.abs_diff_eq(Vec3::new(-1.0, -1.0, 0.0), 1e-6));
+        assert!(ndc.max.abs_diff_eq(Vec3::new(1.0, 1.0, far_depth), 1e-6));
+
+        // Behind the camera.
+        let behind = BoundingBox::from_min_max(Vec3::splat(3.0), Vec3::splat(5.0));
+        assert_eq!(behind.projected_ndc_bounds(&view_projection), None);
+        assert_eq!(
+            BoundingBox::nothing().projected_ndc_bounds(&view_projection),
+            None
+        );
+
+        let projection = Mat4::perspective_infinite_reverse_rh(FRAC_PI_2, 1.0, 1.0);
+        let view_projection = projection * view;
+        let ndc = bb.projected_ndc_bounds_reverse_z(&view_projection).unwrap();
+        let far_depth = view_projection.project_point3(Vec3::new(0.0, 0.0, -8.0)).z;
+        assert!(ndc.min.abs_diff_eq(Vec3::new(-1.0, -1.0, far_depth), 1e-6));
+        assert!(ndc.max.abs_diff_eq(Vec3::new(1.0, 1.0, 1.0), 1e-6));
+        assert_eq!(
+            behind.projected_ndc_bounds_reverse_z(&view_projection),
+            None
+        );
+    }
+
+    #[test]
+    fn test_distance_to_ray() {
+        let bb = BoundingBox::from_min_max(Vec3::ZERO, Vec3::ONE);
+
+        // Passing by the box
+        let ray = Ray3::from_origin_dir(Vec3::new(-5.0, 3.0, 0.5), Vec3::X);
+        assert!((bb.distance_to_ray(&ray) - 2.0).abs() < 1e-6);
+
+        // Passing by an edge of the box
+        let ray = Ray3::from_origin_dir(Vec3::new(-5.0, 2.0, 2.0), Vec3::X);
+        assert!((bb.distance_to_ray(&ray) - 2.0_f32.sqrt()).abs() < 1e-6);
+
+        // Pointing away from the box
+        let ray = Ray3::from_origin_dir(Vec3::new(3.0, 0.5, 0.5), Vec3::X);
+        assert!((bb.distance_to_ray(&ray) - 2.0).abs() < 1e-6);
+
+        // Hitting the box
+        let ray = Ray3::from_origin_dir(Vec3::new(3.0, 0.5, 0.5), -Vec3::X);
+        assert_eq!(bb.distance_to_ray(&ray), 0.0);
+
+        // Diagonal, closest to a corner
+        let ray = Ray3::from_origin_dir(
+            Vec3::new(3.0, 0.0, -1.0),
+            Vec3::new(-1.0, 1.0, 0.0).normalize(),
+        );
+        let expected = (0..1000)
+            .map(|i| bb.distance(ray.point_along(i as f32 * 0.01)))
+            .fold(f32::INFINITY, f32::min);
+        assert!((bb.distance_to_ray(&ray) - expected).abs() < 1e-3);
+    }
+
     #[test]
     fn test_rotated_around_origin() {
         const EPSILON: f32 = 1e-6;
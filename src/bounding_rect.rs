@@ -0,0 +1,371 @@
+use super::Affine2;
+use super::Mat2;
+use super::Vec2;
+
+/// A 2-dimensional axis-aligned bounding rectangle.
+///
+/// The 2D counterpart of [`crate::BoundingBox`].
+#[derive(Clone, Copy, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "speedy", derive(speedy::Writable, speedy::Readable))]
+#[cfg_attr(feature = "speedy", allow(clippy::manual_slice_size_calculation))]
+pub struct BoundingRect {
+    /// Bounding rectangle minimum (inclusive).
+    pub min: Vec2,
+    /// Bounding rectangle maximum (inclusive).
+    pub max: Vec2,
+}
+
+#[cfg(not(target_arch = "spirv"))]
+impl core::fmt::Debug for BoundingRect {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{:?} - {:?}", self.min, self.max)
+    }
+}
+
+impl BoundingRect {
+    /// A [`BoundingRect`] that only contains [`Vec2::ZERO`].
+    pub const ZERO: Self = Self {
+        min: Vec2::ZERO,
+        max: Vec2::ZERO,
+    };
+
+    /// A [`BoundingRect`] that contains no points.
+    ///
+    /// This is useful as the seed for bounding rectangles.
+    #[inline]
+    pub fn nothing() -> Self {
+        Self {
+            min: Vec2::splat(f32::INFINITY),
+            max: Vec2::splat(f32::NEG_INFINITY),
+        }
+    }
+
+    /// A [`BoundingRect`] that contains every point.
+    #[inline]
+    pub fn everything() -> Self {
+        Self {
+            min: Vec2::splat(f32::NEG_INFINITY),
+            max: Vec2::splat(f32::INFINITY),
+        }
+    }
+
+    /// Create a bounding rectangle from a minimum and maximum position.
+    #[inline]
+    pub fn from_min_max(min: Vec2, max: Vec2) -> Self {
+        Self { min, max }
+    }
+
+    /// Create a bounding rectangle from a minimum position and a size.
+    #[inline]
+    pub fn from_min_size(min: Vec2, size: Vec2) -> Self {
+        Self {
+            min,
+            max: min + size,
+        }
+    }
+
+    /// Create a bounding rectangle from a center position and a size.
+    pub fn from_center_size(center: Vec2, size: Vec2) -> Self {
+        Self::from_min_max(center - 0.5 * size, center + 0.5 * size)
+    }
+
+    /// Create a bounding rectangle from an iterator of points that the rectangle will cover.
+    pub fn from_points(points: impl Iterator<Item = Vec2>) -> Self {
+        let mut rect = Self::nothing();
+        for p in points {
+            rect.extend(p);
+        }
+        rect
+    }
+
+    /// Returns the center point of the rectangle.
+    #[inline]
+    pub fn center(&self) -> Vec2 {
+        (self.min + self.max) * 0.5
+    }
+
+    /// Returns the 2D axis size of the rectangle.
+    #[inline]
+    pub fn size(&self) -> Vec2 {
+        self.max - self.min
+    }
+
+    /// Returns half the size (similar to a radius).
+    #[inline]
+    pub fn half_size(&self) -> Vec2 {
+        0.5 * (self.max - self.min)
+    }
+
+    /// Only correct for positively sized rectangles.
+    pub fn area(&self) -> f32 {
+        let s = self.size();
+        s.x * s.y
+    }
+
+    /// True if and only if there is at least one point for which `rect.contains(point)` is true.
+    ///
+    /// Will return `true` if [`Self::min`] == [`Self::max`].
+    /// The opposite of `is_nothing()`.
+    pub fn is_something(&self) -> bool {
+        self.min.x <= self.max.x && self.min.y <= self.max.y
+    }
+
+    /// True if and only if there is no point for which `rect.contains(point)` is true.
+    ///
+    /// The opposite of `is_something()`.
+    pub fn is_nothing(&self) -> bool {
+        self.max.x < self.min.x || self.max.y < self.min.y
+    }
+
+    /// True if this rectangle contains exactly one point.
+    ///
+    /// `true` if [`Self::min`] == [`Self::max`].
+    #[inline]
+    pub fn is_point(&self) -> bool {
+        self.min == self.max
+    }
+
+    /// Returns `true` if, and only if, all elements are finite.
+    ///
+    /// If any element is either `NaN`, positive or negative infinity, this will return `false`.
+    #[inline]
+    pub fn is_finite(&self) -> bool {
+        self.min.is_finite() && self.max.is_finite()
+    }
+
+    /// Returns `true` if any elements are `NaN`.
+    #[inline]
+    pub fn is_nan(&self) -> bool {
+        self.min.is_nan() || self.max.is_nan()
+    }
+
+    /// The four corners of this rectangle, in counter-clockwise order starting at [`Self::min`].
+    pub fn corners(&self) -> [Vec2; 4] {
+        [
+            self.min,
+            Vec2::new(self.max.x, self.min.y),
+            self.max,
+            Vec2::new(self.min.x, self.max.y),
+        ]
+    }
+
+    /// Enlarge the rectangle to include this point.
+    #[inline]
+    pub fn extend(&mut self, pos: Vec2) {
+        self.min = self.min.min(pos);
+        self.max = self.max.max(pos);
+    }
+
+    /// Returns the smallest rectangle that covers both `self` and `other`.
+    #[must_use]
+    pub fn union(mut self, other: Self) -> Self {
+        self.min = self.min.min(other.min);
+        self.max = self.max.max(other.max);
+        self
+    }
+
+    /// Returns the largest area that is covered by both `self` and `other`,
+    /// or [`Self::nothing`] if the rectangles are disjoint.
+    #[must_use]
+    pub fn intersection(self, other: Self) -> Self {
+        let intersection = Self {
+            min: self.min.max(other.min),
+            max: self.max.min(other.max),
+        };
+        if intersection.is_nothing() {
+            Self::nothing()
+        } else {
+            intersection
+        }
+    }
+
+    /// Returns `true` if the point is within (or on the edge of) the rectangle.
+    #[must_use]
+    pub fn contains(&self, point: Vec2) -> bool {
+        (self.min.x <= point.x && point.x <= self.max.x)
+            && (self.min.y <= point.y && point.y <= self.max.y)
+    }
+
+    /// Expand with this much padding on each side.
+    #[must_use]
+    pub fn expanded(&self, padding: Vec2) -> Self {
+        Self {
+            min: self.min - padding,
+            max: self.max + padding,
+        }
+    }
+
+    /// Translate (move) the rectangle by this much.
+    #[must_use]
+    pub fn translated(&self, translation: Vec2) -> Self {
+        Self {
+            min: self.min + translation,
+            max: self.max + translation,
+        }
+    }
+
+    /// Return a bounding rectangle that contains this rectangle after it has been transformed.
+    ///
+    /// Note that the transformed bounding rectangle is very likely larger than the original,
+    /// since it must be large enough to contain the now rotated rectangle.
+    #[must_use]
+    pub fn transform_affine2(&self, m: &Affine2) -> Self {
+        if self.is_nothing() {
+            return Self::nothing();
+        }
+
+        // Inspired by:
+        // https://zeux.io/2010/10/17/aabb-from-obb-with-component-wise-abs
+        let center = m.transform_point2(self.center());
+        let abs_matrix2 = Mat2::from_cols(m.matrix2.x_axis.abs(), m.matrix2.y_axis.abs());
+        let half_size = abs_matrix2.mul_vec2(self.half_size());
+
+        Self {
+            min: center - half_size,
+            max: center + half_size,
+        }
+    }
+
+    /// Intersects the rectangle with a ray using the slab test.
+    ///
+    /// Returns the distances `(t_enter, t_exit)` along the ray where it enters and exits the
+    /// rectangle, or [`None`] if the ray misses the rectangle or the rectangle is behind the ray.
+    /// `t_enter` is negative if the ray starts inside the rectangle.
+    ///
+    /// Any point on the ray can be found through `origin + t * dir`.
+    #[cfg(not(target_arch = "spirv"))] // TODO: large Options in rust-gpu
+    #[inline]
+    pub fn intersect_ray(&self, origin: Vec2, dir: Vec2) -> Option<(f32, f32)> {
+        self.intersect_ray_inv_dir(origin, dir.recip())
+    }
+
+    /// Same as [`Self::intersect_ray`], but takes the precomputed reciprocal of the
+    /// ray direction.
+    ///
+    /// Useful when testing the same ray against many rectangles.
+    #[cfg(not(target_arch = "spirv"))] // TODO: large Options in rust-gpu
+    pub fn intersect_ray_inv_dir(&self, origin: Vec2, inv_dir: Vec2) -> Option<(f32, f32)> {
+        if self.is_nothing() {
+            return None;
+        }
+
+        let mut t_enter = f32::NEG_INFINITY;
+        let mut t_exit = f32::INFINITY;
+
+        for i in 0..2 {
+            if inv_dir[i].is_infinite() {
+                // Parallel to the slab, so we never enter or exit it.
+                if origin[i] < self.min[i] || self.max[i] < origin[i] {
+                    return None;
+                }
+            } else {
+                let t0 = (self.min[i] - origin[i]) * inv_dir[i];
+                let t1 = (self.max[i] - origin[i]) * inv_dir[i];
+                t_enter = t_enter.max(t0.min(t1));
+                t_exit = t_exit.min(t0.max(t1));
+            }
+        }
+
+        (t_enter <= t_exit && 0.0 <= t_exit).then_some((t_enter, t_exit))
+    }
+
+    /// Intersects the rectangle with the line segment from `a` to `b`.
+    ///
+    /// Returns the parameters `(t_enter, t_exit)` of the part of the segment inside the
+    /// rectangle, where `0.0` is `a` and `1.0` is `b`, or [`None`] if the segment
+    /// is fully outside the rectangle.
+    #[cfg(not(target_arch = "spirv"))] // TODO: large Options in rust-gpu
+    pub fn intersect_segment(&self, a: Vec2, b: Vec2) -> Option<(f32, f32)> {
+        let (t_enter, t_exit) = self.intersect_ray(a, b - a)?;
+        let (t_enter, t_exit) = (t_enter.max(0.0), t_exit.min(1.0));
+        (t_enter <= t_exit).then_some((t_enter, t_exit))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::f32::consts::FRAC_PI_4;
+
+    #[test]
+    fn test_bounding_rect() {
+        let rect = BoundingRect::from_points([Vec2::ONE, Vec2::ZERO, Vec2::X].into_iter());
+        assert_eq!(rect, BoundingRect::from_min_max(Vec2::ZERO, Vec2::ONE));
+        assert!(rect.contains(Vec2::splat(0.5)));
+        assert!(!rect.contains(Vec2::splat(1.5)));
+        assert!(rect.expanded(Vec2::splat(0.5)).contains(Vec2::splat(1.5)));
+        assert!(BoundingRect::nothing().is_nothing());
+        assert!(BoundingRect::everything().contains(Vec2::splat(1e30)));
+        assert_eq!(rect.corners(), [Vec2::ZERO, Vec2::X, Vec2::ONE, Vec2::Y]);
+    }
+
+    #[test]
+    fn test_intersection() {
+        assert_eq!(
+            BoundingRect::from_min_max(Vec2::splat(0.0), Vec2::splat(2.0)).intersection(
+                BoundingRect::from_min_max(Vec2::splat(1.0), Vec2::splat(3.0))
+            ),
+            BoundingRect::from_min_max(Vec2::splat(1.0), Vec2::splat(2.0))
+        );
+        assert_eq!(
+            BoundingRect::from_min_max(Vec2::splat(0.0), Vec2::splat(1.0)).intersection(
+                BoundingRect::from_min_max(Vec2::splat(2.0), Vec2::splat(3.0))
+            ),
+            BoundingRect::nothing()
+        );
+        assert_eq!(
+            BoundingRect::nothing().union(BoundingRect::ZERO),
+            BoundingRect::ZERO
+        );
+    }
+
+    #[test]
+    fn test_transform_affine2() {
+        const EPSILON: f32 = 1e-6;
+        const SCALE: f32 = 2.0;
+        let transform =
+            Affine2::from_scale_angle_translation(Vec2::splat(SCALE), FRAC_PI_4, Vec2::ONE);
+        let rect = BoundingRect::from_center_size(Vec2::ZERO, Vec2::ONE);
+        let transformed = rect.transform_affine2(&transform);
+
+        let expected_size = (2.0 * SCALE * SCALE).sqrt();
+        assert!(Vec2::distance(transformed.center(), Vec2::ONE) < EPSILON);
+        assert!(Vec2::distance(transformed.size(), Vec2::splat(expected_size)) < EPSILON);
+        assert!(
+            BoundingRect::nothing()
+                .transform_affine2(&transform)
+                .is_nothing()
+        );
+    }
+
+    #[test]
+    fn test_intersect_ray_and_segment() {
+        #![allow(clippy::float_cmp)]
+        let rect = BoundingRect::from_min_max(Vec2::ONE, Vec2::splat(2.0));
+
+        assert_eq!(
+            rect.intersect_ray(Vec2::new(0.0, 1.5), Vec2::X),
+            Some((1.0, 2.0))
+        );
+        assert_eq!(rect.intersect_ray(Vec2::new(0.0, 1.5), -Vec2::X), None);
+        assert_eq!(rect.intersect_ray(Vec2::new(0.0, 2.5), Vec2::X), None);
+        assert_eq!(
+            BoundingRect::nothing().intersect_ray(Vec2::ZERO, Vec2::X),
+            None
+        );
+
+        assert_eq!(
+            rect.intersect_segment(Vec2::new(0.0, 1.5), Vec2::new(4.0, 1.5)),
+            Some((0.25, 0.5))
+        );
+        assert_eq!(
+            rect.intersect_segment(Vec2::new(1.5, 1.5), Vec2::new(4.0, 1.5)),
+            Some((0.0, 0.2))
+        );
+        assert_eq!(
+            rect.intersect_segment(Vec2::new(0.0, 1.5), Vec2::new(0.5, 1.5)),
+            None
+        );
+    }
+}
@@ -0,0 +1,437 @@
+use crate::BoundingBox;
+use crate::Conformal3;
+use crate::IsoTransform;
+use crate::Vec3;
+
+/// A 3-dimensional bounding sphere.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "speedy", derive(speedy::Writable, speedy::Readable))]
+pub struct BoundingSphere {
+    /// Center of the sphere.
+    pub center: Vec3,
+    /// Radius of the sphere. Negative for a sphere that contains no points.
+    pub radius: f32,
+}
+
+impl BoundingSphere {
+    /// A [`BoundingSphere`] that contains no points.
+    ///
+    /// This is useful as the seed for growing bounding spheres.
+    #[inline]
+    pub fn nothing() -> Self {
+        Self {
+            center: Vec3::ZERO,
+            radius: f32::NEG_INFINITY,
+        }
+    }
+
+    /// Create a bounding sphere from a center position and a radius.
+    #[inline]
+    pub fn from_center_radius(center: Vec3, radius: f32) -> Self {
+        Self { center, radius }
+    }
+
+    /// The sphere centered at the bounding box that fully contains the box.
+    ///
+    /// Returns [`Self::nothing`] if the box contains no points.
+    pub fn from_bounding_box(bb: &BoundingBox) -> Self {
+        if bb.is_nothing() {
+            Self::nothing()
+        } else {
+            Self::from_center_radius(bb.center(), bb.half_size().length())
+        }
+    }
+
+    /// Create an approximate bounding sphere from points using Ritter's algorithm.
+    ///
+    /// This is fast, and the result is usually within 5-20% of the minimal sphere.
+    ///
+    /// Returns [`Self::nothing`] if `points` is empty.
+    pub fn from_points_ritter(points: &[Vec3]) -> Self {
+        let Some(&first) = points.first() else {
+            return Self::nothing();
+        };
+
+        let farthest_from = |from: Vec3| {
+            points
+                .iter()
+                .copied()
+                .max_by(|a, b| {
+                    a.distance_squared(from)
+                        .total_cmp(&b.distance_squared(from))
+                })
+                .unwrap_or(from)
+        };
+        let a = farthest_from(first);
+        let b = farthest_from(a);
+
+        let mut sphere = Self::from_center_radius((a + b) * 0.5, a.distance(b) * 0.5);
+        for &p in points {
+            sphere.extend(p);
+        }
+        sphere
+    }
+
+    /// Create the minimal bounding sphere of the points using Welzl's algorithm.
+    ///
+    /// Runs in expected linear time.
+    ///
+    /// Returns [`Self::nothing`] if `points` is empty.
+    pub fn from_points_welzl(points: &[Vec3]) -> Self {
+        let mut points = points.to_vec();
+        shuffle(&mut points);
+        let end = points.len();
+        welzl_move_to_front(&mut points, end, &mut Vec::with_capacity(4))
+    }
+
+    /// Returns the center point of the sphere.
+    #[inline]
+    pub fn center(&self) -> Vec3 {
+        self.center
+    }
+
+    /// Returns the radius of the sphere.
+    #[inline]
+    pub fn radius(&self) -> f32 {
+        self.radius
+    }
+
+    /// True if and only if there is at least one point for which `sphere.contains(point)` is true.
+    ///
+    /// The opposite of `is_nothing()`.
+    #[inline]
+    pub fn is_something(&self) -> bool {
+        0.0 <= self.radius
+    }
+
+    /// True if and only if there is no point for which `sphere.contains(point)` is true.
+    ///
+    /// The opposite of `is_something()`.
+    #[inline]
+    pub fn is_nothing(&self) -> bool {
+        self.radius < 0.0
+    }
+
+    /// Returns `true` if, and only if, all elements are finite.
+    #[inline]
+    pub fn is_finite(&self) -> bool {
+        self.center.is_finite() && self.radius.is_finite()
+    }
+
+    /// Enlarge the sphere as little as possible to include this point.
+    pub fn extend(&mut self, point: Vec3) {
+        if self.is_nothing() {
+            *self = Self::from_center_radius(point, 0.0);
+            return;
+        }
+
+        let dist = self.center.distance(point);
+        if self.radius < dist {
+            let radius = (self.radius + dist) * 0.5;
+            self.center += (point - self.center) * ((radius - self.radius) / dist);
+            self.radius = radius;
+        }
+    }
+
+    /// Returns the smallest sphere that contains both `self` and `other`.
+    #[must_use]
+    pub fn union(self, other: Self) -> Self {
+        if other.is_nothing() || self.contains_sphere(&other) {
+            return self;
+        }
+        if self.is_nothing() || other.contains_sphere(&self) {
+            return other;
+        }
+
+        let dist = self.center.distance(other.center);
+        let radius = (dist + self.radius + other.radius) * 0.5;
+        let center = self.center + (other.center - self.center) * ((radius - self.radius) / dist);
+        Self { center, radius }
+    }
+
+    /// Returns `true` if the point is within (or on the edge of) the sphere.
+    #[must_use]
+    pub fn contains(&self, point: Vec3) -> bool {
+        self.center.distance(point) <= self.radius
+    }
+
+    /// Returns `true` if `other` is fully within (or touching the edge of) the sphere.
+    ///
+    /// An empty sphere is contained within every sphere.
+    #[must_use]
+    pub fn contains_sphere(&self, other: &Self) -> bool {
+        other.is_nothing() || self.center.distance(other.center) + other.radius <= self.radius
+    }
+
+    /// Returns `true` if the spheres overlap (or touch).
+    #[must_use]
+    pub fn intersects_sphere(&self, other: &Self) -> bool {
+        self.is_something()
+            && other.is_something()
+            && self.center.distance(other.center) <= self.radius + other.radius
+    }
+
+    /// Returns `true` if the sphere overlaps (or touches) the box.
+    #[must_use]
+    pub fn intersects_bounding_box(&self, bb: &BoundingBox) -> bool {
+        bb.is_something() && self.contains(self.center.clamp(bb.min, bb.max))
+    }
+
+    /// The smallest axis-aligned [`BoundingBox`] containing this sphere.
+    pub fn to_bounding_box(&self) -> BoundingBox {
+        if self.is_nothing() {
+            BoundingBox::nothing()
+        } else {
+            BoundingBox::from_center_size(self.center, Vec3::splat(2.0 * self.radius))
+        }
+    }
+
+    /// Return this sphere after it has been transformed.
+    #[must_use]
+    pub fn transform_iso(&self, m: &IsoTransform) -> Self {
+        Self {
+            center: m.transform_point3(self.center),
+            radius: self.radius,
+        }
+    }
+
+    /// Return this sphere after it has been transformed.
+    ///
+    /// The radius is scaled by [`Conformal3::scale`].
+    #[must_use]
+    pub fn transform_conformal3(&self, m: &Conformal3) -> Self {
+        Self {
+            center: m.transform_point3(self.center),
+            radius: self.radius * m.scale().abs(),
+        }
+    }
+}
+
+/// Move-to-front variant of Welzl's algorithm.
+///
+/// Finds the minimal sphere containing `points[..end]` with all of `support` on its boundary.
+/// The recursion depth is bounded by the size of the support set (at most 4).
+fn welzl_move_to_front(points: &mut [Vec3], end: usize, support: &mut Vec<Vec3>) -> BoundingSphere {
+    let mut sphere = sphere_from_support(support);
+    if support.len() == 4 {
+        return sphere;
+    }
+
+    for i in 0..end {
+        let p = points[i];
+        if !contains_with_tolerance(&sphere, p) {
+            support.push(p);
+            sphere = welzl_move_to_front(points, i, support);
+            support.pop();
+            points[..=i].rotate_right(1);
+        }
+    }
+    sphere
+}
+
+/// Relative tolerance to make sure points on the boundary aren't rejected due to rounding.
+fn contains_with_tolerance(sphere: &BoundingSphere, p: Vec3) -> bool {
+    const EPSILON: f32 = 1e-5;
+    sphere.center.distance(p) <= sphere.radius * (1.0 + EPSILON) + EPSILON
+}
+
+/// The smallest sphere with all the given (at most 4) points on its boundary.
+fn sphere_from_support(support: &[Vec3]) -> BoundingSphere {
+    match *support {
+        [] => BoundingSphere::nothing(),
+        [a] => BoundingSphere::from_center_radius(a, 0.0),
+        [a, b] => BoundingSphere::from_center_radius((a + b) * 0.5, a.distance(b) * 0.5),
+        [a, b, c] => circumsphere_of_triangle(a, b, c),
+        [a, b, c, d] => circumsphere_of_tetrahedron(a, b, c, d),
+        _ => unreachable!("support set has at most 4 points"),
+    }
+}
+
+fn circumsphere_of_triangle(a: Vec3, b: Vec3, c: Vec3) -> BoundingSphere {
+    let ab = b - a;
+    let ac = c - a;
+    let n = ab.cross(ac);
+    let denom = 2.0 * n.length_squared();
+
+    if denom <= f32::EPSILON * ab.length_squared() * ac.length_squared() {
+        // Colinear points: the farthest two points span the sphere.
+        return [[a, b], [a, c], [b, c]]
+            .map(|[p, q]| BoundingSphere::from_center_radius((p + q) * 0.5, p.distance(q) * 0.5))
+            .into_iter()
+            .fold(BoundingSphere::nothing(), |best, s| {
+                if best.radius < s.radius { s } else { best }
+            });
+    }
+
+    let offset = (ac.length_squared() * n.cross(ab) + ab.length_squared() * ac.cross(n)) / denom;
+    BoundingSphere::from_center_radius(a + offset, offset.length())
+}
+
+fn circumsphere_of_tetrahedron(a: Vec3, b: Vec3, c: Vec3, d: Vec3) -> BoundingSphere {
+    let u = b - a;
+    let v = c - a;
+    let w = d - a;
+    let denom = 2.0 * u.dot(v.cross(w));
+
+    if denom.abs() <= f32::EPSILON * u.length() * v.length() * w.length() {
+        // Coplanar points: pick the smallest triangle circumsphere containing all points.
+        let candidates = [
+            circumsphere_of_triangle(a, b, c),
+            circumsphere_of_triangle(a, b, d),
+            circumsphere_of_triangle(a, c, d),
+            circumsphere_of_triangle(b, c, d),
+        ];
+        return candidates
+            .into_iter()
+            .filter(|s| [a, b, c, d].iter().all(|&p| contains_with_tolerance(s, p)))
+            .min_by(|s0, s1| s0.radius.total_cmp(&s1.radius))
+            .unwrap_or_else(|| {
+                candidates
+                    .into_iter()
+                    .fold(BoundingSphere::nothing(), BoundingSphere::union)
+            });
+    }
+
+    let offset = (u.length_squared() * v.cross(w)
+        + v.length_squared() * w.cross(u)
+        + w.length_squared() * u.cross(v))
+        / denom;
+    BoundingSphere::from_center_radius(a + offset, offset.length())
+}
+
+/// Deterministic shuffle to avoid the worst case of Welzl's algorithm on sorted input.
+fn shuffle(points: &mut [Vec3]) {
+    // xorshift64
+    let mut state = 0x853c_49e6_748f_ea9b_u64;
+    for i in (1..points.len()).rev() {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        points.swap(i, (state % (i as u64 + 1)) as usize);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::Quat;
+
+    fn test_points() -> Vec<Vec3> {
+        let mut points = vec![];
+        for i in 0..200 {
+            let t = i as f32;
+            points.push(Vec3::new(
+                (t * 0.37).sin() * 3.0,
+                (t * 0.71).cos() * 2.0,
+                (t * 1.13).sin() * (t * 0.05),
+            ));
+        }
+        points
+    }
+
+    #[test]
+    fn test_welzl_is_minimal() {
+        // The points of a regular octahedron have a unique minimal sphere.
+        let points = [
+            Vec3::X,
+            -Vec3::X,
+            Vec3::Y,
+            -Vec3::Y,
+            Vec3::Z,
+            -Vec3::Z,
+            Vec3::ZERO,
+        ]
+        .map(|p| p * 2.0 + Vec3::ONE);
+        let sphere = BoundingSphere::from_points_welzl(&points);
+        assert!(sphere.center.abs_diff_eq(Vec3::ONE, 1e-5));
+        assert!((sphere.radius - 2.0).abs() < 1e-5);
+
+        let points = test_points();
+        let welzl = BoundingSphere::from_points_welzl(&points);
+        let ritter = BoundingSphere::from_points_ritter(&points);
+        for &p in &points {
+            assert!(contains_with_tolerance(&welzl, p));
+            assert!(contains_with_tolerance(&ritter, p));
+        }
+        assert!(welzl.radius <= ritter.radius);
+    }
+
+    #[test]
+    fn test_degenerate_points() {
+        assert!(BoundingSphere::from_points_welzl(&[]).is_nothing());
+        assert!(BoundingSphere::from_points_ritter(&[]).is_nothing());
+
+        let sphere = BoundingSphere::from_points_welzl(&[Vec3::ONE; 5]);
+        assert!(sphere.center.abs_diff_eq(Vec3::ONE, 1e-6));
+        assert!(sphere.radius.abs() < 1e-6);
+
+        // colinear
+        let points = [0.0, 3.0, 1.0, 4.0, 2.0].map(|x| Vec3::new(x, 1.0, 1.0));
+        let sphere = BoundingSphere::from_points_welzl(&points);
+        assert!(sphere.center.abs_diff_eq(Vec3::new(2.0, 1.0, 1.0), 1e-5));
+        assert!((sphere.radius - 2.0).abs() < 1e-5);
+
+        // coplanar
+        let points = [
+            Vec3::X,
+            Vec3::Y,
+            -Vec3::X,
+            -Vec3::Y,
+            Vec3::new(0.5, 0.5, 0.0),
+        ];
+        let sphere = BoundingSphere::from_points_welzl(&points);
+        assert!(sphere.center.abs_diff_eq(Vec3::ZERO, 1e-5));
+        assert!((sphere.radius - 1.0).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_union() {
+        let a = BoundingSphere::from_center_radius(Vec3::ZERO, 1.0);
+        let b = BoundingSphere::from_center_radius(Vec3::new(4.0, 0.0, 0.0), 1.0);
+        let u = a.union(b);
+        assert!(u.center.abs_diff_eq(Vec3::new(2.0, 0.0, 0.0), 1e-6));
+        assert!((u.radius - 3.0).abs() < 1e-6);
+        assert!(u.contains_sphere(&a) && u.contains_sphere(&b));
+
+        let inner = BoundingSphere::from_center_radius(Vec3::new(0.2, 0.0, 0.0), 0.5);
+        assert_eq!(a.union(inner), a);
+        assert_eq!(inner.union(a), a);
+        assert_eq!(a.union(BoundingSphere::nothing()), a);
+        assert_eq!(BoundingSphere::nothing().union(a), a);
+
+        assert!(!a.intersects_sphere(&b));
+        assert!(a.intersects_sphere(&inner));
+        assert!(!a.intersects_sphere(&BoundingSphere::nothing()));
+    }
+
+    #[test]
+    fn test_intersects_bounding_box() {
+        let sphere = BoundingSphere::from_center_radius(Vec3::ZERO, 1.0);
+        let bb = BoundingBox::from_min_max(Vec3::splat(0.5), Vec3::splat(2.0));
+        assert!(sphere.intersects_bounding_box(&bb));
+        let bb = BoundingBox::from_min_max(Vec3::splat(0.6), Vec3::splat(2.0));
+        assert!(!sphere.intersects_bounding_box(&bb));
+        assert!(!sphere.intersects_bounding_box(&BoundingBox::nothing()));
+    }
+
+    #[test]
+    fn test_transform() {
+        let sphere = BoundingSphere::from_center_radius(Vec3::X, 2.0);
+        let rotation = Quat::from_rotation_z(std::f32::consts::FRAC_PI_2);
+
+        let iso = IsoTransform::from_rotation_translation(rotation, Vec3::Z);
+        let transformed = sphere.transform_iso(&iso);
+        assert!(transformed.center.abs_diff_eq(Vec3::Y + Vec3::Z, 1e-6));
+        assert!((transformed.radius - 2.0).abs() < 1e-6);
+
+        let conformal = Conformal3::from_scale_rotation_translation(3.0, rotation, Vec3::Z);
+        let transformed = sphere.transform_conformal3(&conformal);
+        assert!(
+            transformed
+                .center
+                .abs_diff_eq(3.0 * Vec3::Y + Vec3::Z, 1e-6)
+        );
+        assert!((transformed.radius - 6.0).abs() < 1e-6);
+    }
+}
@@ -0,0 +1,524 @@
+use crate::BoundingBox;
+use crate::Ray3;
+use crate::Vec3;
+
+/// A node in a [`Bvh`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BvhNode {
+    /// Bounds of everything below this node.
+    pub bounds: BoundingBox,
+    /// For leaves: index of the first primitive in [`Bvh::indices`].
+    /// For interior nodes: index of the first child. The second child follows directly after.
+    first: u32,
+    /// Number of primitives in a leaf, or 0 for interior nodes.
+    count: u32,
+}
+
+impl BvhNode {
+    /// True if this node has no children, only primitives.
+    #[inline]
+    pub fn is_leaf(&self) -> bool {
+        self.count > 0
+    }
+
+    /// The indices of the two children, if this is an interior node.
+    #[inline]
+    pub fn children(&self) -> Option<[usize; 2]> {
+        (!self.is_leaf()).then(|| [self.first as usize, self.first as usize + 1])
+    }
+
+    /// The range into [`Bvh::indices`] of the primitives in this node, if this is a leaf.
+    #[inline]
+    pub fn primitives(&self) -> Option<core::ops::Range<usize>> {
+        self.is_leaf()
+            .then(|| self.first as usize..(self.first + self.count) as usize)
+    }
+}
+
+/// A static bounding volume hierarchy over a set of [`BoundingBox`]es.
+///
+/// Built top-down using a binned surface area heuristic (SAH) and stored as a flat array of
+/// nodes, where the root is the first node.
+///
+/// The hierarchy only knows about the bounding boxes of the primitives.
+/// Queries report the index of each primitive into the slice the hierarchy was built from,
+/// and it is up to the caller to do any exact tests against the primitives themselves.
+#[derive(Clone, Debug, Default)]
+pub struct Bvh {
+    nodes: Vec<BvhNode>,
+    indices: Vec<u32>,
+    boxes: Vec<BoundingBox>,
+}
+
+impl Bvh {
+    /// Leaves with this many primitives or fewer are never split.
+    const MAX_LEAF_SIZE: u32 = 4;
+
+    /// Number of buckets used when evaluating the SAH.
+    const NUM_BINS: usize = 16;
+
+    /// Cost of traversing a node relative to testing a primitive.
+    const TRAVERSAL_COST: f32 = 1.0;
+
+    /// Build a hierarchy over `boxes`.
+    ///
+    /// The boxes should be well-formed (see [`BoundingBox::is_something`]) and finite.
+    pub fn build(boxes: &[BoundingBox]) -> Self {
+        let mut bvh = Self {
+            nodes: Vec::with_capacity((2 * boxes.len()).saturating_sub(1)),
+            indices: (0..boxes.len() as u32).collect(),
+            boxes: boxes.to_vec(),
+        };
+        if boxes.is_empty() {
+            return bvh;
+        }
+
+        let centroids: Vec<Vec3> = boxes.iter().map(BoundingBox::center).collect();
+
+        bvh.nodes.push(BvhNode {
+            bounds: boxes
+                .iter()
+                .fold(BoundingBox::nothing(), |a, b| a.union(*b)),
+            first: 0,
+            count: boxes.len() as u32,
+        });
+
+        let mut stack = vec![0];
+        while let Some(node_index) = stack.pop() {
+            if let Some([left, right]) = bvh.split(node_index, boxes, &centroids) {
+                stack.push(left);
+                stack.push(right);
+            }
+        }
+
+        bvh
+    }
+
+    /// Try splitting a leaf in two using the SAH, returning the new children if it was split.
+    fn split(
+        &mut self,
+        node_index: usize,
+        boxes: &[BoundingBox],
+        centroids: &[Vec3],
+    ) -> Option<[usize; 2]> {
+        let node = self.nodes[node_index];
+        if node.count <= Self::MAX_LEAF_SIZE {
+            return None;
+        }
+
+        let primitives = &mut self.indices[node.primitives()?];
+        let centroid_bounds =
+            BoundingBox::from_points(primitives.iter().map(|&i| centroids[i as usize]));
+
+        let bin_of = |axis: usize, i: u32| {
+            let extent = centroid_bounds.max[axis] - centroid_bounds.min[axis];
+            let t = (centroids[i as usize][axis] - centroid_bounds.min[axis]) / extent;
+            ((t * Self::NUM_BINS as f32) as usize).min(Self::NUM_BINS - 1)
+        };
+
+        // (cost, axis, first bin on the right side)
+        let mut best_split: Option<(f32, usize, usize)> = None;
+
+        for axis in 0..3 {
+            if centroid_bounds.max[axis] <= centroid_bounds.min[axis] {
+                continue;
+            }
+
+            let mut bins = [(BoundingBox::nothing(), 0_u32); Self::NUM_BINS];
+            for &i in primitives.iter() {
+                let bin = &mut bins[bin_of(axis, i)];
+                bin.0 = bin.0.union(boxes[i as usize]);
+                bin.1 += 1;
+            }
+
+            // Sweep from the right to get the cost of everything right of each split...
+            let mut right_costs = [0.0; Self::NUM_BINS];
+            let mut bounds = BoundingBox::nothing();
+            let mut count = 0;
+            for split in (1..Self::NUM_BINS).rev() {
+                bounds = bounds.union(bins[split].0);
+                count += bins[split].1;
+                right_costs[split] = if count == 0 {
+                    0.0
+                } else {
+                    bounds.surface_area() * count as f32
+                };
+            }
+
+            // ...then from the left, combining them.
+            let mut bounds = BoundingBox::nothing();
+            let mut count = 0;
+            for split in 1..Self::NUM_BINS {
+                bounds = bounds.union(bins[split - 1].0);
+                count += bins[split - 1].1;
+                if count == 0 || count == node.count {
+                    continue;
+                }
+                let cost = bounds.surface_area() * count as f32 + right_costs[split];
+                if best_split.is_none_or(|(best_cost, _, _)| cost < best_cost) {
+                    best_split = Some((cost, axis, split));
+                }
+            }
+        }
+
+        let (cost, axis, split) = best_split?;
+        let leaf_cost = node.bounds.surface_area() * node.count as f32;
+        let split_cost = Self::TRAVERSAL_COST * node.bounds.surface_area() + cost;
+        if leaf_cost <= split_cost {
+            return None;
+        }
+
+        // Partition the primitives in place.
+        let mut left_count = 0;
+        for i in 0..primitives.len() {
+            if bin_of(axis, primitives[i]) < split {
+                primitives.swap(i, left_count);
+                left_count += 1;
+            }
+        }
+
+        let (left, right) = primitives.split_at(left_count);
+        let bounds_of = |primitives: &[u32]| {
+            primitives
+                .iter()
+                .fold(BoundingBox::nothing(), |a, &i| a.union(boxes[i as usize]))
+        };
+        let children = [
+            BvhNode {
+                bounds: bounds_of(left),
+                first: node.first,
+                count: left_count as u32,
+            },
+            BvhNode {
+                bounds: bounds_of(right),
+                first: node.first + left_count as u32,
+                count: node.count - left_count as u32,
+            },
+        ];
+
+        let first_child = self.nodes.len();
+        self.nodes.extend(children);
+        self.nodes[node_index] = BvhNode {
+            bounds: node.bounds,
+            first: first_child as u32,
+            count: 0,
+        };
+
+        Some([first_child, first_child + 1])
+    }
+
+    /// The nodes of the hierarchy. The first node is the root.
+    #[inline]
+    pub fn nodes(&self) -> &[BvhNode] {
+        &self.nodes
+    }
+
+    /// The primitive indices referenced by the leaf nodes.
+    #[inline]
+    pub fn indices(&self) -> &[u32] {
+        &self.indices
+    }
+
+    /// The bounds of all primitives, or [`BoundingBox::nothing`] if there are none.
+    #[inline]
+    pub fn bounds(&self) -> BoundingBox {
+        self.nodes
+            .first()
+            .map_or_else(BoundingBox::nothing, |root| root.bounds)
+    }
+
+    /// True if the hierarchy contains no primitives.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.indices.is_empty()
+    }
+
+    /// Find the nearest primitive hit by the ray.
+    ///
+    /// `intersect` is called with the index of each primitive whose bounding box is hit
+    /// (roughly front to back, skipping those that are farther away than the nearest hit so far)
+    /// and should return the distance along the ray to the primitive,
+    /// or [`None`] if the primitive was missed.
+    ///
+    /// Returns the index of the nearest primitive and its distance,
+    /// considering only hits in the range `[0, max_t]`.
+    pub fn nearest_ray_hit(
+        &self,
+        ray: &Ray3,
+        max_t: f32,
+        mut intersect: impl FnMut(usize) -> Option<f32>,
+    ) -> Option<(usize, f32)> {
+        let inv_dir = ray.inv_dir();
+        let t_enter_box = |bb: &BoundingBox| {
+            bb.intersect_ray_inv_dir(ray.origin, inv_dir)
+                .map(|(t_enter, _)| t_enter)
+        };
+        let t_enter = |node: &BvhNode| t_enter_box(&node.bounds);
+
+        let mut nearest = None;
+        let mut nearest_t = max_t;
+
+        let mut stack = Vec::with_capacity(64);
+        if let Some(t) = self.nodes.first().and_then(t_enter) {
+            stack.push((0, t));
+        }
+
+        while let Some((node_index, node_t)) = stack.pop() {
+            if nearest_t < node_t {
+                continue;
+            }
+
+            let node = &self.nodes[node_index];
+            if let Some(primitives) = node.primitives() {
+                for &i in &self.indices[primitives] {
+                    if !t_enter_box(&self.boxes[i as usize]).is_some_and(|t| t <= nearest_t) {
+                        continue;
+                    }
+                    let t = intersect(i as usize).filter(|t| (0.0..=nearest_t).contains(t));
+                    if let Some(t) = t {
+                        nearest = Some((i as usize, t));
+                        nearest_t = t;
+                    }
+                }
+            } else if let Some([left, right]) = node.children() {
+                let left_t = t_enter(&self.nodes[left]);
+                let right_t = t_enter(&self.nodes[right]);
+                // Push the farther child first so that the nearer one is visited first.
+                let mut children = [(left, left_t), (right, right_t)];
+                if left_t < right_t {
+                    children.swap(0, 1);
+                }
+                for (child, t) in children {
+                    if let Some(t) = t.filter(|&t| t <= nearest_t) {
+                        stack.push((child, t));
+                    }
+                }
+            }
+        }
+
+        nearest
+    }
+
+    /// Find the primitive nearest to `point`.
+    ///
+    /// `distance` is called for primitives whose bounding box is close enough to possibly be
+    /// nearer than the nearest one found so far, and should return the exact distance from
+    /// `point` to the primitive, or [`None`] to ignore the primitive.
+    ///
+    /// Returns the index of the nearest primitive and its distance,
+    /// considering only primitives within `max_distance`.
+    pub fn nearest_to_point(
+        &self,
+        point: Vec3,
+        max_distance: f32,
+        mut distance: impl FnMut(usize) -> Option<f32>,
+    ) -> Option<(usize, f32)> {
+        let node_distance = |node: &BvhNode| node.bounds.distance(point);
+
+        let mut nearest = None;
+        let mut nearest_distance = max_distance;
+
+        let mut stack = Vec::with_capacity(64);
+        if let Some(root) = self.nodes.first() {
+            stack.push((0, node_distance(root)));
+        }
+
+        while let Some((node_index, node_dist)) = stack.pop() {
+            if nearest_distance < node_dist {
+                continue;
+            }
+
+            let node = &self.nodes[node_index];
+            if let Some(primitives) = node.primitives() {
+                for &i in &self.indices[primitives] {
+                    if nearest_distance < self.boxes[i as usize].distance(point) {
+                        continue;
+                    }
+                    let dist = distance(i as usize).filter(|&d| d <= nearest_distance);
+                    if let Some(dist) = dist {
+                        nearest = Some((i as usize, dist));
+                        nearest_distance = dist;
+                    }
+                }
+            } else if let Some([left, right]) = node.children() {
+                let left_dist = node_distance(&self.nodes[left]);
+                let right_dist = node_distance(&self.nodes[right]);
+                // Push the farther child first so that the nearer one is visited first.
+                let mut children = [(left, left_dist), (right, right_dist)];
+                if left_dist < right_dist {
+                    children.swap(0, 1);
+                }
+                for (child, dist) in children {
+                    if dist <= nearest_distance {
+                        stack.push((child, dist));
+                    }
+                }
+            }
+        }
+
+        nearest
+    }
+
+    /// Call `callback` with the index of every primitive whose bounding box
+    /// is hit by the ray within the range `[0, max_t]`.
+    pub fn for_each_ray_hit(&self, ray: &Ray3, max_t: f32, callback: impl FnMut(usize)) {
+        let inv_dir = ray.inv_dir();
+        self.for_each_in_nodes(
+            |bounds| {
+                bounds
+                    .intersect_ray_inv_dir(ray.origin, inv_dir)
+                    .is_some_and(|(t_enter, _)| t_enter <= max_t)
+            },
+            callback,
+        );
+    }
+
+    /// Call `callback` with the index of every primitive whose bounding box
+    /// overlaps (or touches) `bb`.
+    pub fn for_each_intersecting(&self, bb: &BoundingBox, callback: impl FnMut(usize)) {
+        self.for_each_in_nodes(|bounds| bounds.intersects(bb), callback);
+    }
+
+    /// Call `callback` with the index of every primitive whose bounding box contains `point`.
+    pub fn for_each_containing(&self, point: Vec3, callback: impl FnMut(usize)) {
+        self.for_each_in_nodes(|bounds| bounds.contains(point), callback);
+    }
+
+    /// Visit every primitive whose bounding box, and all of its ancestors', pass `test`.
+    fn for_each_in_nodes(
+        &self,
+        mut test: impl FnMut(&BoundingBox) -> bool,
+        mut callback: impl FnMut(usize),
+    ) {
+        if self.nodes.is_empty() {
+            return;
+        }
+
+        let mut stack = Vec::with_capacity(64);
+        stack.push(0);
+        while let Some(node_index) = stack.pop() {
+            let node = &self.nodes[node_index];
+            if !test(&node.bounds) {
+                continue;
+            }
+
+            if let Some(primitives) = node.primitives() {
+                for &i in &self.indices[primitives] {
+                    if test(&self.boxes[i as usize]) {
+                        callback(i as usize);
+                    }
+                }
+            } else if let Some([left, right]) = node.children() {
+                stack.push(right);
+                stack.push(left);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test_utils::Rng;
+
+    fn random_boxes(rng: &mut Rng, count: usize) -> Vec<BoundingBox> {
+        (0..count).map(|_| rng.next_box()).collect()
+    }
+
+    fn sorted(mut v: Vec<usize>) -> Vec<usize> {
+        v.sort_unstable();
+        v
+    }
+
+    #[test]
+    fn test_structure() {
+        let mut rng = Rng(0x853c_49e6_748f_ea9b);
+        let boxes = random_boxes(&mut rng, 1000);
+        let bvh = Bvh::build(&boxes);
+
+        assert_eq!(
+            sorted(bvh.indices().iter().map(|&i| i as usize).collect()),
+            (0..1000).collect::<Vec<_>>()
+        );
+        assert!(bvh.nodes().len() > 1);
+
+        for node in bvh.nodes() {
+            if let Some(primitives) = node.primitives() {
+                for &i in &bvh.indices()[primitives] {
+                    let bb = boxes[i as usize];
+                    assert_eq!(node.bounds.union(bb), node.bounds);
+                }
+            } else if let Some(children) = node.children() {
+                for child in children {
+                    let child_bounds = bvh.nodes()[child].bounds;
+                    assert_eq!(node.bounds.union(child_bounds), node.bounds);
+                }
+            }
+        }
+
+        assert!(Bvh::build(&[]).is_empty());
+        assert!(Bvh::build(&[]).bounds().is_nothing());
+    }
+
+    #[test]
+    fn test_queries_match_brute_force() {
+        let mut rng = Rng(0xda3e_39cb_94b9_5bdb);
+        let boxes = random_boxes(&mut rng, 500);
+        let bvh = Bvh::build(&boxes);
+
+        for _ in 0..100 {
+            let ray =
+                Ray3::from_origin_dir(100.0 * rng.next_vec3(), (rng.next_vec3() - 0.5).normalize());
+            let max_t = 80.0;
+            let hit_t = |i: usize| boxes[i].intersect_ray(&ray).map(|(t, _)| t.max(0.0));
+
+            let expected_nearest = (0..boxes.len())
+                .filter_map(|i| hit_t(i).map(|t| (i, t)))
+                .filter(|&(_, t)| t <= max_t)
+                .min_by(|a, b| a.1.total_cmp(&b.1));
+            assert_eq!(bvh.nearest_ray_hit(&ray, max_t, hit_t), expected_nearest);
+
+            let mut all = vec![];
+            bvh.for_each_ray_hit(&ray, max_t, |i| all.push(i));
+            let expected_all: Vec<usize> = (0..boxes.len())
+                .filter(|&i| hit_t(i).is_some_and(|t| t <= max_t))
+                .collect();
+            assert_eq!(sorted(all), expected_all);
+
+            let query = BoundingBox::from_center_size(100.0 * rng.next_vec3(), Vec3::splat(10.0));
+            let mut overlapping = vec![];
+            bvh.for_each_intersecting(&query, |i| overlapping.push(i));
+            let expected: Vec<usize> = (0..boxes.len())
+                .filter(|&i| boxes[i].intersects(&query))
+                .collect();
+            assert_eq!(sorted(overlapping), expected);
+
+            let point = 100.0 * rng.next_vec3();
+            let distance = |i: usize| Some(boxes[i].center().distance(point));
+            let expected_nearest = (0..boxes.len())
+                .filter_map(|i| distance(i).map(|d| (i, d)))
+                .min_by(|a, b| a.1.total_cmp(&b.1));
+            assert_eq!(
+                bvh.nearest_to_point(point, f32::INFINITY, distance),
+                expected_nearest
+            );
+
+            let mut containing = vec![];
+            bvh.for_each_containing(point, |i| containing.push(i));
+            let expected: Vec<usize> = (0..boxes.len())
+                .filter(|&i| boxes[i].contains(point))
+                .collect();
+            assert_eq!(sorted(containing), expected);
+        }
+    }
+
+    #[test]
+    fn test_identical_boxes() {
+        // All centroids are the same, so no split is possible.
+        let boxes = vec![BoundingBox::from_center_size(Vec3::ONE, Vec3::ONE); 100];
+        let bvh = Bvh::build(&boxes);
+        let mut count = 0;
+        bvh.for_each_containing(Vec3::ONE, |_| count += 1);
+        assert_eq!(count, 100);
+    }
+}
@@ -0,0 +1,113 @@
+use crate::BoundingBox;
+use crate::Ray3;
+use crate::RayHit;
+use crate::Raycast;
+use crate::Segment3;
+use crate::Vec3;
+
+/// A capsule: every point within `radius` of the line segment between `a` and `b`.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "speedy", derive(speedy::Writable, speedy::Readable))]
+pub struct Capsule3 {
+    /// Center of one of the spherical caps.
+    pub a: Vec3,
+    /// Center of the other spherical cap.
+    pub b: Vec3,
+    /// Radius of the caps and the cylinder between them.
+    pub radius: f32,
+}
+
+impl Capsule3 {
+    /// Create a capsule around the segment between `a` and `b`.
+    #[inline]
+    pub fn new(a: Vec3, b: Vec3, radius: f32) -> Self {
+        Self { a, b, radius }
+    }
+
+    /// The segment running through the middle of the capsule.
+    #[inline]
+    pub fn segment(&self) -> Segment3 {
+        Segment3::new(self.a, self.b)
+    }
+
+    /// Returns `true` if the point is within (or on the surface of) the capsule.
+    #[inline]
+    pub fn contains(&self, point: Vec3) -> bool {
+        self.segment().distance_to_point(point) <= self.radius
+    }
+
+    /// The smallest box containing the capsule.
+    #[inline]
+    pub fn bounding_box(&self) -> BoundingBox {
+        BoundingBox::from_min_max(self.a.min(self.b), self.a.max(self.b))
+            .expanded(Vec3::splat(self.radius))
+    }
+}
+
+impl Raycast for Capsule3 {
+    fn raycast(&self, ray: &Ray3, max_t: f32) -> Option<RayHit> {
+        let (t, normal) = ray.intersect_capsule(self.a, self.b, self.radius)?;
+        (t <= max_t).then(|| RayHit::from_ray_t_normal(ray, t, normal))
+    }
+}
+
+impl core::ops::Mul<Capsule3> for crate::IsoTransform {
+    type Output = Capsule3;
+
+    fn mul(self, rhs: Capsule3) -> Capsule3 {
+        Capsule3 {
+            a: self.transform_point3(rhs.a),
+            b: self.transform_point3(rhs.b),
+            radius: rhs.radius,
+        }
+    }
+}
+
+impl core::ops::Mul<Capsule3> for crate::Conformal3 {
+    type Output = Capsule3;
+
+    fn mul(self, rhs: Capsule3) -> Capsule3 {
+        Capsule3 {
+            a: self.transform_point3(rhs.a),
+            b: self.transform_point3(rhs.b),
+            radius: self.scale().abs() * rhs.radius,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::Conformal3;
+    use crate::Quat;
+
+    #[test]
+    fn test_capsule() {
+        #![allow(clippy::float_cmp)]
+        let capsule = Capsule3::new(Vec3::ZERO, Vec3::new(0.0, 2.0, 0.0), 0.5);
+        assert!(capsule.contains(Vec3::new(0.5, 1.0, 0.0)));
+        assert!(capsule.contains(Vec3::new(0.0, 2.5, 0.0)));
+        assert!(!capsule.contains(Vec3::new(0.4, 2.4, 0.0)));
+        assert_eq!(
+            capsule.bounding_box(),
+            BoundingBox::from_min_max(Vec3::new(-0.5, -0.5, -0.5), Vec3::new(0.5, 2.5, 0.5))
+        );
+
+        let ray = Ray3::from_origin_dir(Vec3::new(-5.0, 1.0, 0.0), Vec3::X);
+        let hit = capsule.raycast(&ray, 10.0).unwrap();
+        assert_eq!(hit.t, 4.5);
+        assert_eq!(hit.normal, -Vec3::X);
+
+        let conformal = Conformal3::from_scale_rotation_translation(2.0, Quat::IDENTITY, Vec3::X);
+        assert_eq!(
+            conformal * capsule,
+            Capsule3::new(Vec3::X, Vec3::new(1.0, 4.0, 0.0), 1.0)
+        );
+        let mirror = Conformal3::from_scale_rotation_translation(-2.0, Quat::IDENTITY, Vec3::X);
+        assert_eq!(
+            mirror * capsule,
+            Capsule3::new(Vec3::X, Vec3::new(1.0, -4.0, 0.0), 1.0)
+        );
+    }
+}
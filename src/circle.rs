@@ -0,0 +1,131 @@
+use crate::Vec2;
+
+#[cfg(target_arch = "spirv")]
+use num_traits::Float;
+
+/// A circle in 2-dimensional space, or the disk it encloses.
+#[derive(Clone, Copy, Default, PartialEq)]
+#[cfg_attr(not(target_arch = "spirv"), derive(Debug))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "speedy", derive(speedy::Writable, speedy::Readable))]
+pub struct Circle {
+    /// Center of the circle.
+    pub center: Vec2,
+    /// Radius of the circle.
+    pub radius: f32,
+}
+
+impl Circle {
+    /// Create a circle from its center and radius.
+    #[inline]
+    pub fn from_center_radius(center: Vec2, radius: f32) -> Self {
+        Self { center, radius }
+    }
+
+    /// True if every value is finite
+    #[inline]
+    pub fn is_finite(&self) -> bool {
+        self.center.is_finite() && self.radius.is_finite()
+    }
+
+    /// Returns `true` if the point is within (or on the edge of) the circle.
+    #[inline]
+    pub fn contains(&self, point: Vec2) -> bool {
+        self.center.distance_squared(point) <= self.radius * self.radius
+    }
+
+    /// The signed distance from the edge of the circle to `point`,
+    /// which is negative inside the circle.
+    #[inline]
+    pub fn signed_distance(&self, point: Vec2) -> f32 {
+        self.center.distance(point) - self.radius
+    }
+
+    /// Returns the point within the circle closest to `point`,
+    /// which is `point` itself if the circle contains it.
+    #[inline]
+    pub fn closest_point(&self, point: Vec2) -> Vec2 {
+        let offset = point - self.center;
+        if offset.length_squared() <= self.radius * self.radius {
+            point
+        } else {
+            self.center + offset.normalize() * self.radius
+        }
+    }
+
+    /// Returns `true` if the two circles overlap or touch.
+    #[inline]
+    pub fn intersects_circle(&self, other: &Self) -> bool {
+        let radius = self.radius + other.radius;
+        self.center.distance_squared(other.center) <= radius * radius
+    }
+
+    /// The points where the edges of the two circles cross.
+    ///
+    /// The first point is to the right of, and the second to the left of, the line from
+    /// `self.center` to `other.center`. The points are the same if the circles just touch.
+    /// Returns [`None`] if the edges don't cross, which includes when one circle is
+    /// inside the other, or when the circles are the same.
+    #[cfg(not(target_arch = "spirv"))] // TODO: large Options in rust-gpu
+    pub fn intersect_circle(&self, other: &Self) -> Option<(Vec2, Vec2)> {
+        let offset = other.center - self.center;
+        let distance = offset.length();
+        if distance == 0.0
+            || distance > self.radius + other.radius
+            || distance < (self.radius - other.radius).abs()
+        {
+            return None;
+        }
+        let dir = offset / distance;
+        // Distance from `self.center` to the chord between the crossing points.
+        let along = (self.radius * self.radius - other.radius * other.radius + distance * distance)
+            / (2.0 * distance);
+        let half_chord = (self.radius * self.radius - along * along).max(0.0).sqrt();
+        let mid = self.center + dir * along;
+        Some((mid - dir.perp() * half_chord, mid + dir.perp() * half_chord))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_circle() {
+        #![allow(clippy::float_cmp)]
+        let circle = Circle::from_center_radius(Vec2::new(1.0, 1.0), 2.0);
+        assert!(circle.contains(Vec2::new(3.0, 1.0)));
+        assert!(!circle.contains(Vec2::new(3.0, 2.0)));
+        assert_eq!(circle.signed_distance(Vec2::new(1.0, 5.0)), 2.0);
+        assert_eq!(circle.signed_distance(Vec2::new(0.0, 1.0)), -1.0);
+        assert_eq!(
+            circle.closest_point(Vec2::new(1.0, 5.0)),
+            Vec2::new(1.0, 3.0)
+        );
+        assert_eq!(
+            circle.closest_point(Vec2::new(0.0, 1.0)),
+            Vec2::new(0.0, 1.0)
+        );
+
+        let other = Circle::from_center_radius(Vec2::new(4.0, 1.0), 2.0);
+        assert!(circle.intersects_circle(&other));
+        let (right, left) = circle.intersect_circle(&other).unwrap();
+        let half_chord = 1.75_f32.sqrt();
+        assert!(right.abs_diff_eq(Vec2::new(2.5, 1.0 - half_chord), 1e-6));
+        assert!(left.abs_diff_eq(Vec2::new(2.5, 1.0 + half_chord), 1e-6));
+
+        let touching = Circle::from_center_radius(Vec2::new(5.0, 1.0), 2.0);
+        assert_eq!(
+            circle.intersect_circle(&touching),
+            Some((Vec2::new(3.0, 1.0), Vec2::new(3.0, 1.0)))
+        );
+
+        let far = Circle::from_center_radius(Vec2::new(6.0, 1.0), 2.0);
+        assert!(!circle.intersects_circle(&far));
+        assert_eq!(circle.intersect_circle(&far), None);
+        let inner = Circle::from_center_radius(Vec2::new(1.5, 1.0), 0.5);
+        assert!(circle.intersects_circle(&inner));
+        assert_eq!(circle.intersect_circle(&inner), None);
+        assert_eq!(circle.intersect_circle(&circle), None);
+    }
+}
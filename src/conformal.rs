@@ -2,6 +2,9 @@ use glam::Affine3A;
 use glam::Mat4;
 use glam::Vec3A;
 
+#[cfg(target_arch = "spirv")]
+use num_traits::Float;
+
 use crate::IsoTransform;
 use crate::Quat;
 use crate::Vec3;
@@ -240,6 +243,43 @@ impl Conformal3 {
     pub fn is_finite(&self) -> bool {
         self.translation_and_scale.is_finite() && self.rotation.is_finite()
     }
+
+    /// Interpolates between `self` (at `t = 0`) and `other` (at `t = 1`).
+    ///
+    /// The translation is linearly interpolated, the rotation is spherically interpolated taking
+    /// the shortest path, and the scale is geometrically interpolated, so that going from a scale
+    /// of 1 to 4 passes 2 halfway. Both scales should be positive.
+    /// See [`Self::sclerp`] for constant-velocity interpolation.
+    #[inline]
+    #[must_use]
+    pub fn lerp(&self, other: &Self, t: f32) -> Self {
+        Self::from_scale_rotation_translation(
+            self.scale().powf(1.0 - t) * other.scale().powf(t),
+            self.rotation.slerp(other.rotation, t),
+            self.translation().lerp(other.translation(), t),
+        )
+    }
+
+    /// Screw linear interpolation (`ScLERP`) between `self` (at `t = 0`) and `other` (at `t = 1`).
+    ///
+    /// Like [`IsoTransform::sclerp`], the motion has constant velocity and the rotation takes
+    /// the shortest path. With scaling, points move along spirals, and the scale is
+    /// geometrically interpolated as in [`Self::lerp`]. Both scales should be positive.
+    #[must_use]
+    pub fn sclerp(&self, other: &Self, t: f32) -> Self {
+        let relative = self.inverse() * *other;
+        let (rotation, translation) = crate::iso_transform::screw_pow(
+            relative.rotation,
+            relative.scale(),
+            relative.translation(),
+            t,
+        );
+        self * Self::from_scale_rotation_translation(
+            relative.scale().powf(t),
+            rotation,
+            translation,
+        )
+    }
 }
 
 impl core::ops::Mul for &Conformal3 {
@@ -383,4 +423,75 @@ mod test {
         let identity = transform * transform.inverse();
         assert_approx_eq_transform!(identity, Conformal3::IDENTITY);
     }
+
+    #[test]
+    fn test_lerp() {
+        use std::f32::consts::TAU;
+        let a = Conformal3::from_scale_rotation_translation(1.0, Quat::IDENTITY, Vec3::X);
+        let b = Conformal3::from_scale_rotation_translation(
+            4.0,
+            Quat::from_rotation_z(TAU / 4.0),
+            Vec3::new(3.0, 2.0, 0.0),
+        );
+        assert_approx_eq_transform!(a.lerp(&b, 0.0), a);
+        assert_approx_eq_transform!(a.lerp(&b, 1.0), b);
+        assert_approx_eq_transform!(
+            a.lerp(&b, 0.5),
+            Conformal3::from_scale_rotation_translation(
+                2.0,
+                Quat::from_rotation_z(TAU / 8.0),
+                Vec3::new(2.0, 1.0, 0.0)
+            )
+        );
+    }
+
+    #[test]
+    fn test_sclerp() {
+        use std::f32::consts::TAU;
+        let t = [
+            Conformal3::IDENTITY,
+            Conformal3::from_scale_rotation_translation(0.5, Quat::IDENTITY, Vec3::ONE),
+            Conformal3::from_scale_rotation_translation(
+                3.0,
+                Quat::from_axis_angle(Vec3::new(0.3, -0.5, -0.4).normalize(), 2.5),
+                Vec3::new(0.7, 1.2, 3.4),
+            ),
+            Conformal3::from_scale_rotation_translation(
+                1.0,
+                Quat::from_axis_angle(Vec3::new(-0.8, -0.1, 0.2).normalize(), 1.0),
+                Vec3::new(-3.6, 4.2, -0.7),
+            ),
+        ];
+        for a in &t {
+            for b in &t {
+                assert_approx_eq_transform!(a.sclerp(b, 0.0), *a);
+                let end = a.sclerp(b, 1.0);
+                assert!(end.translation().abs_diff_eq(b.translation(), 1e-5));
+                assert!((end.scale() - b.scale()).abs() < 1e-5);
+                // Halfway twice is the whole way.
+                let half = a.sclerp(b, 0.5);
+                let twice = half * (a.inverse() * half);
+                assert!(twice.translation().abs_diff_eq(b.translation(), 1e-5));
+                assert!((twice.scale() - b.scale()).abs() < 1e-5);
+            }
+        }
+
+        // Spiralling in towards `center` while turning around it.
+        let center = Vec3::new(1.0, 2.0, 3.0);
+        let spiral = |s: f32| {
+            Conformal3::from_translation(center)
+                * Conformal3::from_scale_rotation_translation(
+                    0.25_f32.powf(s),
+                    Quat::from_rotation_z(s * TAU / 4.0),
+                    Vec3::ZERO,
+                )
+                * Conformal3::from_translation(-center)
+        };
+        let a = Conformal3::IDENTITY;
+        let b = spiral(1.0);
+        for i in 0..=4 {
+            let s = i as f32 / 4.0;
+            assert_approx_eq_transform!(a.sclerp(&b, s), spiral(s));
+        }
+    }
 }
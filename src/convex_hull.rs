@@ -0,0 +1,481 @@
+use crate::DPlane3;
+use crate::DVec3;
+use crate::Plane3;
+use crate::Vec3;
+
+/// The convex hull of a set of points, as a closed triangle mesh.
+///
+/// The triangles index into the points the hull was built from, in the same layout as
+/// [`crate::MeshGen`]: three indices per triangle, counter-clockwise when seen from outside.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ConvexHull {
+    /// Three indices per triangle into the points the hull was built from.
+    pub indices: Vec<u32>,
+    /// The normalized plane of each triangle, with the normal pointing out of the hull.
+    pub planes: Vec<Plane3>,
+}
+
+struct Face {
+    vertices: [u32; 3],
+    /// The face on the other side of the edge from `vertices[i]` to `vertices[(i + 1) % 3]`.
+    neighbors: [u32; 3],
+    plane: Plane3,
+    /// Points that are above this face, and not yet part of the hull.
+    outside: Vec<u32>,
+    alive: bool,
+}
+
+impl Face {
+    fn new(vertices: [u32; 3], points: &[Vec3]) -> Self {
+        Self {
+            vertices,
+            neighbors: [u32::MAX; 3],
+            plane: plane_through(vertices.map(|i| points[i as usize])),
+            outside: vec![],
+            alive: true,
+        }
+    }
+
+    fn centroid(&self, points: &[Vec3]) -> Vec3 {
+        self.vertices
+            .iter()
+            .map(|&i| points[i as usize])
+            .sum::<Vec3>()
+            / 3.0
+    }
+}
+
+/// An edge of the region of faces that can see the eye, between `a` and `b`
+/// in the winding order of the visible face.
+struct HorizonEdge {
+    a: u32,
+    b: u32,
+    /// The face on the other side, which can't see the eye.
+    outer: u32,
+    /// The index of the edge in `outer`.
+    outer_edge: usize,
+}
+
+impl ConvexHull {
+    /// Compute the convex hull of the points using the quickhull algorithm.
+    ///
+    /// Duplicate points, and points on the surface of the hull (within a small tolerance
+    /// relative to the size of the point cloud), are not used as hull vertices,
+    /// so that flat sides are not needlessly subdivided.
+    ///
+    /// Returns [`None`] if the points do not span a volume, i.e. if there are fewer than four
+    /// points, or if they all lie in a plane.
+    pub fn from_points(points: &[Vec3]) -> Option<Self> {
+        let eps = tolerance(points)?;
+        let mut faces = initial_tetrahedron(points, eps)?;
+
+        // Assign every point to a face it is outside of.
+        let mut new_faces: Vec<u32> = (0..faces.len() as u32).collect();
+        assign_outside_points(&mut faces, &new_faces, 0..points.len() as u32, points, eps);
+
+        // Faces that may have points outside them.
+        let mut work = new_faces.clone();
+        // Dead faces, which can be reused.
+        let mut free = vec![];
+        let mut visible = vec![];
+        let mut horizon = vec![];
+        let mut orphans = vec![];
+
+        while let Some(face_index) = work.pop() {
+            let face = &faces[face_index as usize];
+            if !face.alive || face.outside.is_empty() {
+                continue;
+            }
+            let eye = furthest_point(
+                face.outside.iter().copied(),
+                points,
+                |p| face.plane.distance(p),
+                face.centroid(points),
+                eps,
+            );
+            let eye_pos = points[eye as usize];
+
+            // Remove all faces that can see the eye, keeping their outside points.
+            find_horizon(
+                &mut faces,
+                face_index,
+                eye_pos,
+                points,
+                &mut visible,
+                &mut horizon,
+            );
+            for &i in &visible {
+                orphans.append(&mut faces[i as usize].outside);
+            }
+            free.extend_from_slice(&visible);
+
+            // Connect each horizon edge to the eye.
+            new_faces.clear();
+            for edge in &horizon {
+                let mut face = Face::new([edge.a, edge.b, eye], points);
+                face.neighbors[0] = edge.outer;
+                let index = if let Some(index) = free.pop() {
+                    faces[index as usize] = face;
+                    index
+                } else {
+                    faces.push(face);
+                    faces.len() as u32 - 1
+                };
+                faces[edge.outer as usize].neighbors[edge.outer_edge] = index;
+                new_faces.push(index);
+            }
+            // The horizon is a loop, so each new face shares its other edges with the
+            // new faces before and after it.
+            let num_new = new_faces.len();
+            for (i, &index) in new_faces.iter().enumerate() {
+                let neighbors = &mut faces[index as usize].neighbors;
+                neighbors[1] = new_faces[(i + 1) % num_new];
+                neighbors[2] = new_faces[(i + num_new - 1) % num_new];
+            }
+
+            // The edges along the horizon can be very slightly concave, so points can be
+            // inside the new faces but outside the faces just beyond them.
+            orphans.retain(|&i| i != eye);
+            new_faces.extend(horizon.iter().map(|edge| edge.outer));
+            assign_outside_points(&mut faces, &new_faces, orphans.drain(..), points, eps);
+            work.extend(
+                new_faces
+                    .iter()
+                    .filter(|&&i| !faces[i as usize].outside.is_empty()),
+            );
+        }
+
+        let mut hull = Self::default();
+        for face in faces.into_iter().filter(|face| face.alive) {
+            hull.indices.extend(face.vertices);
+            hull.planes.push(face.plane);
+        }
+        Some(hull)
+    }
+
+    /// The number of triangles.
+    #[inline]
+    pub fn num_triangles(&self) -> usize {
+        self.planes.len()
+    }
+
+    /// The indices of the points that are vertices of the hull, sorted.
+    pub fn vertex_indices(&self) -> Vec<u32> {
+        let mut vertices = self.indices.clone();
+        vertices.sort_unstable();
+        vertices.dedup();
+        vertices
+    }
+}
+
+/// How far outside the hull a point must be to count, relative to the size of the point cloud.
+///
+/// Returns [`None`] if any point is non-finite.
+fn tolerance(points: &[Vec3]) -> Option<f32> {
+    let max_abs = points.iter().fold(Vec3::ZERO, |max, p| max.max(p.abs()));
+    max_abs
+        .is_finite()
+        .then_some(3.0 * f32::EPSILON * (max_abs.x + max_abs.y + max_abs.z))
+}
+
+/// The plane through a triangle, with the normal facing the side where it is counter-clockwise.
+///
+/// This is computed in double precision, as rounding errors in the normals of long and thin
+/// triangles would otherwise tilt their planes by much more than the tolerance.
+fn plane_through(triangle: [Vec3; 3]) -> Plane3 {
+    let [a, b, c] = triangle.map(|p| p.as_dvec3());
+    let normal = (b - a).cross(c - a).normalize_or_zero();
+    DPlane3::from_normal_point(normal, a).to_plane3_relative_to(DVec3::ZERO)
+}
+
+/// The largest tetrahedron we can easily find among the points,
+/// as four connected faces with outward normals.
+fn initial_tetrahedron(points: &[Vec3], eps: f32) -> Option<Vec<Face>> {
+    if points.len() < 4 {
+        return None;
+    }
+    let all_points = || 0..points.len() as u32;
+    let pos = |i: u32| points[i as usize];
+    let center = points.iter().sum::<Vec3>() / points.len() as f32;
+
+    // The two extreme points along the axis with the largest extent.
+    let mut best = (0, 0, f32::NEG_INFINITY);
+    for axis in 0..3 {
+        let min = furthest_point(all_points(), points, |p| -p[axis], center, eps);
+        let max = furthest_point(all_points(), points, |p| p[axis], center, eps);
+        let extent = pos(max)[axis] - pos(min)[axis];
+        if extent > best.2 {
+            best = (min, max, extent);
+        }
+    }
+    let (a, b, extent) = best;
+    if extent <= eps {
+        return None;
+    }
+
+    // The point furthest from the line through `a` and `b`.
+    let ab = (pos(b) - pos(a)).normalize();
+    let line_dist = |p: Vec3| (p - pos(a)).reject_from_normalized(ab).length();
+    let center = pos(a).midpoint(pos(b));
+    let c = furthest_point(all_points(), points, line_dist, center, eps);
+    if line_dist(pos(c)) <= eps {
+        return None;
+    }
+
+    // The point furthest from the plane through `a`, `b` and `c`.
+    let base = Plane3::from_points(pos(a), pos(b), pos(c));
+    let plane_dist = |p: Vec3| base.distance(p).abs();
+    let center = (pos(a) + pos(b) + pos(c)) / 3.0;
+    let d = furthest_point(all_points(), points, plane_dist, center, eps);
+    if plane_dist(pos(d)) <= eps {
+        return None;
+    }
+
+    // Make the base face away from `d`.
+    let (b, c) = if base.distance(pos(d)) > 0.0 {
+        (c, b)
+    } else {
+        (b, c)
+    };
+
+    let mut faces: Vec<Face> = [[a, b, c], [a, d, b], [b, d, c], [c, d, a]]
+        .into_iter()
+        .map(|vertices| Face::new(vertices, points))
+        .collect();
+    for i in 0..faces.len() {
+        for edge in 0..3 {
+            let [from, to] = [edge, (edge + 1) % 3].map(|v| faces[i].vertices[v]);
+            faces[i].neighbors[edge] = faces
+                .iter()
+                .position(|other| {
+                    (0..3).any(|v| other.vertices[v] == to && other.vertices[(v + 1) % 3] == from)
+                })
+                .unwrap_or_default() as u32;
+        }
+    }
+    Some(faces)
+}
+
+/// Find the faces that can see the eye, starting at `start` and growing the region through
+/// neighboring faces, and mark them as dead.
+///
+/// Faces that the eye is above by less than the tolerance are included too, so that flat sides
+/// are merged instead of collecting needless vertices, and so that the new faces never dip below
+/// the old ones. The region is always connected, so its edges form a single loop.
+///
+/// The edges around the region are written to `horizon`, in order around the loop.
+fn find_horizon(
+    faces: &mut [Face],
+    start: u32,
+    eye: Vec3,
+    points: &[Vec3],
+    visible: &mut Vec<u32>,
+    horizon: &mut Vec<HorizonEdge>,
+) {
+    visible.clear();
+    horizon.clear();
+
+    faces[start as usize].alive = false;
+    visible.push(start);
+    // Depth first, with the next edge to cross and how many edges are left for each face.
+    // Crossing the edges in winding order makes the horizon come out in order.
+    let mut stack = vec![(start, 0, 3)];
+    while let Some((face, next_edge, edges_left)) = stack.last_mut() {
+        if *edges_left == 0 {
+            stack.pop();
+            continue;
+        }
+        let face = *face;
+        let edge = *next_edge % 3;
+        *next_edge += 1;
+        *edges_left -= 1;
+
+        let neighbor = faces[face as usize].neighbors[edge];
+        let neighbor_face = &faces[neighbor as usize];
+        if !neighbor_face.alive {
+            continue; // Already visible.
+        }
+        let outer_edge = neighbor_face
+            .neighbors
+            .iter()
+            .position(|&i| i == face)
+            .unwrap_or_default();
+        let vertices = faces[face as usize].vertices;
+        let (a, b) = (vertices[edge], vertices[(edge + 1) % 3]);
+
+        // The edge between the neighbor and the new face would be concave if either is above
+        // the other. Both tests are needed, as thin triangles make one or the other unreliable.
+        let opposite = neighbor_face.vertices[(outer_edge + 2) % 3];
+        let new_plane = plane_through([points[a as usize], points[b as usize], eye]);
+        if 0.0 <= neighbor_face.plane.distance(eye)
+            || 0.0 < new_plane.distance(points[opposite as usize])
+        {
+            faces[neighbor as usize].alive = false;
+            visible.push(neighbor);
+            // Continue after the edge we came in through.
+            stack.push((neighbor, outer_edge + 1, 2));
+        } else {
+            horizon.push(HorizonEdge {
+                a,
+                b,
+                outer: neighbor,
+                outer_edge,
+            });
+        }
+    }
+    debug_assert!(
+        (0..horizon.len()).all(|i| horizon[i].b == horizon[(i + 1) % horizon.len()].a),
+        "The horizon should be a single loop"
+    );
+}
+
+/// The candidate with the largest `dist`.
+///
+/// Points on an edge or side of the hull can be (nearly) as far as its corners,
+/// so among those we pick the one furthest from `center`, which is always a corner.
+fn furthest_point(
+    candidates: impl Iterator<Item = u32> + Clone,
+    points: &[Vec3],
+    dist: impl Fn(Vec3) -> f32,
+    center: Vec3,
+    eps: f32,
+) -> u32 {
+    let dist = |i: u32| dist(points[i as usize]);
+    let max_dist = candidates.clone().map(dist).fold(f32::MIN, f32::max);
+    candidates
+        .filter(|&i| max_dist - eps <= dist(i))
+        .max_by(|&a, &b| {
+            let from_center = |i: u32| points[i as usize].distance_squared(center);
+            from_center(a).total_cmp(&from_center(b))
+        })
+        .unwrap_or_default()
+}
+
+/// Assign each point to the face among `new_faces` that it is furthest outside of.
+/// Points inside all of them are dropped, as they can never be on the hull.
+fn assign_outside_points(
+    faces: &mut [Face],
+    new_faces: &[u32],
+    candidates: impl IntoIterator<Item = u32>,
+    points: &[Vec3],
+    eps: f32,
+) {
+    for i in candidates {
+        let p = points[i as usize];
+        let mut best: Option<(u32, f32)> = None;
+        for &face_index in new_faces {
+            let dist = faces[face_index as usize].plane.distance(p);
+            if eps < dist && best.is_none_or(|(_, best_dist)| best_dist < dist) {
+                best = Some((face_index, dist));
+            }
+        }
+        if let Some((face_index, _)) = best {
+            faces[face_index as usize].outside.push(i);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::BoundingBox;
+    use crate::IVec3;
+    use crate::test_utils::Rng;
+    use std::collections::HashSet;
+
+    /// Check that the hull is closed, convex and contains all points.
+    fn validate(hull: &ConvexHull, points: &[Vec3]) {
+        assert_eq!(hull.indices.len(), 3 * hull.num_triangles());
+
+        let edges: HashSet<(u32, u32)> = hull
+            .indices
+            .chunks_exact(3)
+            .flat_map(|t| [(t[0], t[1]), (t[1], t[2]), (t[2], t[0])])
+            .collect();
+        assert_eq!(edges.len(), hull.indices.len(), "Duplicate edges");
+        for &(a, b) in &edges {
+            assert!(edges.contains(&(b, a)), "Hull is not closed");
+        }
+
+        // Euler's formula for a closed triangle mesh.
+        let num_vertices = hull.vertex_indices().len();
+        assert_eq!(hull.num_triangles(), 2 * num_vertices - 4);
+
+        let eps = tolerance(points).unwrap();
+        for (triangle, plane) in hull.indices.chunks_exact(3).zip(&hull.planes) {
+            for &i in triangle {
+                assert!(plane.distance(points[i as usize]).abs() <= eps);
+            }
+            for &p in points {
+                assert!(
+                    plane.distance(p) <= eps,
+                    "Point outside hull by {} > {eps}",
+                    plane.distance(p)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_cube_with_coplanar_and_duplicate_points() {
+        let bb = BoundingBox::from_min_max(Vec3::splat(-1.0), Vec3::ONE);
+        let mut rng = Rng(0x2545_f491_4f6c_dd1d);
+        let mut points = bb.corners().to_vec();
+        points.extend(bb.corners());
+        for _ in 0..200 {
+            // Inside, and on the faces of the cube.
+            let p = 2.0 * rng.next_vec3() - 1.0;
+            points.push(p);
+            points.push(Vec3::new(1.0, p.y, p.z));
+            points.push(Vec3::new(p.x, -1.0, p.z));
+        }
+        // A point in the middle of an edge.
+        points.push(Vec3::new(0.0, 1.0, 1.0));
+
+        let hull = ConvexHull::from_points(&points).unwrap();
+        validate(&hull, &points);
+        assert_eq!(hull.num_triangles(), 12);
+        for i in hull.vertex_indices() {
+            assert!(bb.corners().contains(&points[i as usize]));
+        }
+    }
+
+    #[test]
+    fn test_grid() {
+        let points: Vec<Vec3> = crate::IBoundingBox::from_min_size(IVec3::ZERO, IVec3::splat(5))
+            .cells()
+            .map(|cell| cell.as_vec3() * 0.1)
+            .collect();
+        let hull = ConvexHull::from_points(&points).unwrap();
+        validate(&hull, &points);
+        assert_eq!(hull.vertex_indices().len(), 8);
+    }
+
+    #[test]
+    fn test_sphere() {
+        let mut rng = Rng(0x9e37_79b9_7f4a_7c15);
+        let points: Vec<Vec3> = (0..10_000)
+            .map(|_| (2.0 * rng.next_vec3() - 1.0).normalize_or(Vec3::X) * 10.0)
+            .collect();
+        let hull = ConvexHull::from_points(&points).unwrap();
+        validate(&hull, &points);
+        assert!(hull.vertex_indices().len() > 5000);
+    }
+
+    #[test]
+    fn test_degenerate() {
+        assert_eq!(ConvexHull::from_points(&[]), None);
+        assert_eq!(ConvexHull::from_points(&[Vec3::ONE; 10]), None);
+        let on_line: Vec<Vec3> = (0..10).map(|i| Vec3::splat(i as f32)).collect();
+        assert_eq!(ConvexHull::from_points(&on_line), None);
+        let on_plane: Vec<Vec3> = (0..10)
+            .map(|i| Vec3::new(i as f32, (i * i) as f32, 2.0))
+            .collect();
+        assert_eq!(ConvexHull::from_points(&on_plane), None);
+
+        let tetrahedron = [Vec3::ZERO, Vec3::X, Vec3::Y, Vec3::Z];
+        let hull = ConvexHull::from_points(&tetrahedron).unwrap();
+        validate(&hull, &tetrahedron);
+        assert_eq!(hull.num_triangles(), 4);
+    }
+}
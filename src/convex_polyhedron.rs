@@ -0,0 +1,407 @@
+use crate::BoundingBox;
+use crate::Conformal3;
+use crate::Containment;
+use crate::ConvexHull;
+use crate::Frustum;
+use crate::IsoTransform;
+use crate::Plane3;
+use crate::Ray3;
+use crate::RayHit;
+use crate::Raycast;
+use crate::Vec3;
+use crate::frustum::classify_bounding_box_by_planes;
+
+/// A convex volume defined as the intersection of half-spaces,
+/// such as a trigger volume or a light volume.
+///
+/// Like for [`Frustum`], the plane normals point inwards:
+/// a point is inside if its distance to every plane is non-negative.
+/// Without any planes, the volume contains everything.
+///
+/// The corners of the volume can optionally be cached using [`Self::with_vertices`],
+/// which is needed for [`Self::support`] and makes [`Self::classify_bounding_box`] tighter.
+/// To use a polyhedron with [`crate::gjk_distance`] and friends, pass its cached
+/// [`Self::vertices`], which implement [`crate::Support`].
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ConvexPolyhedron {
+    planes: Vec<Plane3>,
+    vertices: Option<Vec<Vec3>>,
+}
+
+impl ConvexPolyhedron {
+    /// Create a polyhedron from normalized planes, with normals pointing inwards.
+    ///
+    /// No vertices are cached.
+    pub fn from_planes(planes: impl Into<Vec<Plane3>>) -> Self {
+        Self {
+            planes: planes.into(),
+            vertices: None,
+        }
+    }
+
+    /// A polyhedron covering the same volume as the box, with its corners cached.
+    pub fn from_bounding_box(bb: &BoundingBox) -> Self {
+        Self {
+            planes: vec![
+                Plane3::from_normal_point(Vec3::X, bb.min),
+                Plane3::from_normal_point(-Vec3::X, bb.max),
+                Plane3::from_normal_point(Vec3::Y, bb.min),
+                Plane3::from_normal_point(-Vec3::Y, bb.max),
+                Plane3::from_normal_point(Vec3::Z, bb.min),
+                Plane3::from_normal_point(-Vec3::Z, bb.max),
+            ],
+            vertices: Some(bb.corners().to_vec()),
+        }
+    }
+
+    /// A polyhedron covering the same volume as the hull of `points`, with its vertices cached.
+    ///
+    /// There is one plane per hull triangle, so flat sides made of several triangles
+    /// result in duplicate planes.
+    pub fn from_convex_hull(hull: &ConvexHull, points: &[Vec3]) -> Self {
+        Self {
+            planes: hull.planes.iter().map(Plane3::flipped).collect(),
+            vertices: Some(
+                hull.vertex_indices()
+                    .into_iter()
+                    .map(|i| points[i as usize])
+                    .collect(),
+            ),
+        }
+    }
+
+    /// A polyhedron covering the same volume as the frustum.
+    ///
+    /// No vertices are cached.
+    pub fn from_frustum(frustum: &Frustum) -> Self {
+        Self::from_planes(frustum.planes)
+    }
+
+    /// Cache the vertices of the polyhedron, see [`Self::compute_vertices`].
+    #[must_use]
+    pub fn with_vertices(mut self) -> Self {
+        self.vertices = Some(self.compute_vertices());
+        self
+    }
+
+    /// The normalized planes, with normals pointing inwards.
+    #[inline]
+    pub fn planes(&self) -> &[Plane3] {
+        &self.planes
+    }
+
+    /// The cached vertices, if any.
+    #[inline]
+    pub fn vertices(&self) -> Option<&[Vec3]> {
+        self.vertices.as_deref()
+    }
+
+    /// Find the corners of the polyhedron, where three or more of its planes meet.
+    ///
+    /// Unbounded polyhedra only get their finite corners.
+    /// This tests every triple of planes, so it is slow for polyhedra with many planes.
+    pub fn compute_vertices(&self) -> Vec<Vec3> {
+        let planes = &self.planes;
+        let mut vertices: Vec<Vec3> = vec![];
+        for i in 0..planes.len() {
+            for j in i + 1..planes.len() {
+                for k in j + 1..planes.len() {
+                    let Some(p) =
+                        Plane3::intersect_three_planes(&planes[i], &planes[j], &planes[k])
+                    else {
+                        continue;
+                    };
+                    let tolerance = 1e-5 * p.abs().max_element().max(1.0);
+                    if p.is_finite()
+                        && planes.iter().all(|plane| -tolerance <= plane.distance(p))
+                        && !vertices
+                            .iter()
+                            .any(|v| v.distance_squared(p) <= tolerance * tolerance)
+                    {
+                        vertices.push(p);
+                    }
+                }
+            }
+        }
+        vertices
+    }
+
+    /// Returns `true` if the point is within (or on the surface of) the polyhedron.
+    #[must_use]
+    pub fn contains(&self, point: Vec3) -> bool {
+        self.planes.iter().all(|plane| 0.0 <= plane.distance(point))
+    }
+
+    /// Intersects the polyhedron with a ray.
+    ///
+    /// Returns the distances `(t_enter, t_exit)` along the ray where it enters and exits the
+    /// polyhedron, or [`None`] if the ray misses it or it is behind the ray.
+    /// `t_enter` is negative if the ray starts inside, and `t_exit` is infinite if the ray
+    /// never leaves an unbounded polyhedron.
+    #[inline]
+    pub fn intersect_ray(&self, ray: &Ray3) -> Option<(f32, f32)> {
+        let ((t_enter, _), (t_exit, _)) = self.clip_ray(ray)?;
+        Some((t_enter, t_exit))
+    }
+
+    /// Like [`Self::intersect_ray`], but also returns the indices of the planes
+    /// where the ray enters and exits, if any.
+    #[allow(clippy::type_complexity)]
+    fn clip_ray(&self, ray: &Ray3) -> Option<((f32, Option<usize>), (f32, Option<usize>))> {
+        let mut enter = (f32::NEG_INFINITY, None);
+        let mut exit = (f32::INFINITY, None);
+
+        for (i, plane) in self.planes.iter().enumerate() {
+            let dist = plane.distance(ray.origin);
+            let speed = plane.normal.dot(ray.dir);
+            if speed == 0.0 {
+                // Parallel to the plane, so we never enter or exit it.
+                if dist < 0.0 {
+                    return None;
+                }
+            } else {
+                let t = -dist / speed;
+                if 0.0 < speed {
+                    if enter.0 < t {
+                        enter = (t, Some(i));
+                    }
+                } else if t < exit.0 {
+                    exit = (t, Some(i));
+                }
+            }
+        }
+
+        (enter.0 <= exit.0 && 0.0 <= exit.0).then_some((enter, exit))
+    }
+
+    /// Classify a bounding box against the polyhedron.
+    ///
+    /// This is conservative: boxes just outside a corner or edge of the polyhedron
+    /// may be classified as [`Containment::Intersecting`].
+    /// Cached vertices make this tighter, as they are used to reject boxes
+    /// that miss the bounds of the polyhedron.
+    pub fn classify_bounding_box(&self, bb: &BoundingBox) -> Containment {
+        if bb.is_nothing() {
+            return Containment::Outside;
+        }
+        let vertex_bounds = self
+            .vertices()
+            .map(|vertices| BoundingBox::from_points(vertices.iter().copied()));
+        if vertex_bounds.is_some_and(|bounds| !bounds.intersects(bb)) {
+            return Containment::Outside;
+        }
+        classify_bounding_box_by_planes(&self.planes, bb)
+    }
+
+    /// Returns `true` if the box and the polyhedron (possibly) overlap.
+    ///
+    /// Conservative in the same way as [`Self::classify_bounding_box`].
+    #[inline]
+    pub fn intersects_bounding_box(&self, bb: &BoundingBox) -> bool {
+        self.classify_bounding_box(bb) != Containment::Outside
+    }
+
+    /// The point of the polyhedron furthest along `dir`, among the cached vertices.
+    ///
+    /// Returns [`None`] if no vertices are cached (see [`Self::with_vertices`]),
+    /// or if there are none, which is the case for some unbounded polyhedra.
+    /// Other unbounded polyhedra only have their finite corners considered.
+    pub fn support(&self, dir: Vec3) -> Option<Vec3> {
+        self.vertices()?
+            .iter()
+            .copied()
+            .max_by(|a, b| a.dot(dir).total_cmp(&b.dot(dir)))
+    }
+
+    /// Return this polyhedron after it has been transformed.
+    #[must_use]
+    pub fn transform_iso(&self, m: &IsoTransform) -> Self {
+        Self {
+            planes: self.planes.iter().map(|p| p.transform_iso(m)).collect(),
+            vertices: self
+                .vertices
+                .as_ref()
+                .map(|vertices| vertices.iter().map(|&v| m.transform_point3(v)).collect()),
+        }
+    }
+
+    /// Return this polyhedron after it has been transformed.
+    #[must_use]
+    pub fn transform_conformal3(&self, m: &Conformal3) -> Self {
+        Self {
+            planes: self
+                .planes
+                .iter()
+                .map(|p| p.transform_conformal3(m))
+                .collect(),
+            vertices: self
+                .vertices
+                .as_ref()
+                .map(|vertices| vertices.iter().map(|&v| m.transform_point3(v)).collect()),
+        }
+    }
+}
+
+impl Raycast for ConvexPolyhedron {
+    fn raycast(&self, ray: &Ray3, max_t: f32) -> Option<RayHit> {
+        let (enter, exit) = self.clip_ray(ray)?;
+        let (t, plane) = if 0.0 <= enter.0 { enter } else { exit };
+        let normal = -self.planes[plane?].normal.normalize();
+        (t <= max_t).then(|| RayHit::from_ray_t_normal(ray, t, normal))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::Quat;
+
+    fn unit_cube() -> ConvexPolyhedron {
+        ConvexPolyhedron::from_bounding_box(&BoundingBox::from_min_max(Vec3::ZERO, Vec3::ONE))
+    }
+
+    #[test]
+    fn test_vertices() {
+        let cube = unit_cube();
+        let mut computed = ConvexPolyhedron::from_planes(cube.planes().to_vec())
+            .with_vertices()
+            .vertices()
+            .unwrap()
+            .to_vec();
+        computed.sort_by(|a, b| a.to_array().partial_cmp(&b.to_array()).unwrap());
+        assert_eq!(computed, cube.vertices().unwrap());
+
+        // A tetrahedron, with an extra plane that does not touch it.
+        let tetrahedron = ConvexPolyhedron::from_planes([
+            Plane3::from_normal_point(Vec3::X, Vec3::ZERO),
+            Plane3::from_normal_point(Vec3::Y, Vec3::ZERO),
+            Plane3::from_normal_point(Vec3::Z, Vec3::ZERO),
+            Plane3::from_normal_point(-Vec3::ONE.normalize(), Vec3::X),
+            Plane3::from_normal_point(-Vec3::X, Vec3::splat(2.0)),
+        ]);
+        assert_eq!(tetrahedron.compute_vertices().len(), 4);
+        assert_eq!(tetrahedron.support(Vec3::X), None);
+        assert_eq!(
+            tetrahedron
+                .with_vertices()
+                .support(Vec3::new(1.0, 0.1, 0.1)),
+            Some(Vec3::X)
+        );
+
+        // A half-space has no corners.
+        let half_space =
+            ConvexPolyhedron::from_planes([Plane3::from_normal_point(Vec3::X, Vec3::ZERO)]);
+        assert!(half_space.compute_vertices().is_empty());
+        assert_eq!(half_space.with_vertices().support(Vec3::X), None);
+    }
+
+    #[test]
+    fn test_contains_and_classify() {
+        let cube = unit_cube();
+        assert!(cube.contains(Vec3::splat(0.5)));
+        assert!(cube.contains(Vec3::ONE));
+        assert!(!cube.contains(Vec3::new(0.5, 0.5, 1.1)));
+
+        let classify = |min, max| cube.classify_bounding_box(&BoundingBox::from_min_max(min, max));
+        assert_eq!(
+            classify(Vec3::splat(0.25), Vec3::splat(0.75)),
+            Containment::Inside
+        );
+        assert_eq!(
+            classify(Vec3::splat(0.5), Vec3::splat(2.0)),
+            Containment::Intersecting
+        );
+        assert_eq!(
+            classify(Vec3::splat(2.0), Vec3::splat(3.0)),
+            Containment::Outside
+        );
+        assert_eq!(
+            cube.classify_bounding_box(&BoundingBox::nothing()),
+            Containment::Outside
+        );
+
+        // A box just past the slanted side of a wedge.
+        let wedge = ConvexPolyhedron::from_planes(vec![
+            Plane3::from_normal_point(Vec3::X, Vec3::ZERO),
+            Plane3::from_normal_point(Vec3::Y, Vec3::ZERO),
+            Plane3::from_normal_point(Vec3::Z, Vec3::ZERO),
+            Plane3::from_normal_point(-Vec3::Z, Vec3::ONE),
+            Plane3::from_normal_point(-Vec3::new(1.0, 1.0, 0.0).normalize(), Vec3::X),
+        ]);
+        let bb = BoundingBox::from_min_max(Vec3::new(0.8, 0.8, 0.0), Vec3::ONE);
+        assert!(!wedge.intersects_bounding_box(&bb));
+        assert!(
+            wedge.intersects_bounding_box(&BoundingBox::from_center_size(Vec3::ZERO, Vec3::ONE))
+        );
+
+        let frustum = ConvexPolyhedron::from_frustum(&Frustum::from_view_projection(
+            &crate::Mat4::orthographic_rh(-1.0, 1.0, -1.0, 1.0, 0.0, 10.0),
+        ));
+        assert!(frustum.contains(Vec3::new(0.5, 0.5, -5.0)));
+        assert!(!frustum.contains(Vec3::new(0.5, 0.5, 5.0)));
+        assert_eq!(frustum.compute_vertices().len(), 8);
+    }
+
+    #[test]
+    fn test_ray() {
+        #![allow(clippy::float_cmp)]
+        let cube = unit_cube();
+        let ray = Ray3::from_origin_dir(Vec3::new(-1.0, 0.5, 0.5), Vec3::X);
+        assert_eq!(cube.intersect_ray(&ray), Some((1.0, 2.0)));
+        let hit = cube.raycast(&ray, 10.0).unwrap();
+        assert_eq!(hit.t, 1.0);
+        assert_eq!(hit.normal, -Vec3::X);
+        assert_eq!(cube.raycast(&ray, 0.5), None);
+
+        // From inside, we hit the exit.
+        let inside = Ray3::from_origin_dir(Vec3::splat(0.5), Vec3::Y);
+        assert_eq!(cube.intersect_ray(&inside), Some((-0.5, 0.5)));
+        assert_eq!(cube.raycast(&inside, 10.0).unwrap().normal, Vec3::Y);
+
+        let miss = Ray3::from_origin_dir(Vec3::new(-1.0, 2.0, 0.5), Vec3::X);
+        assert_eq!(cube.intersect_ray(&miss), None);
+        let behind = Ray3::from_origin_dir(Vec3::new(-1.0, 0.5, 0.5), -Vec3::X);
+        assert_eq!(cube.intersect_ray(&behind), None);
+
+        // Never leaving a half-space.
+        let half_space =
+            ConvexPolyhedron::from_planes([Plane3::from_normal_point(Vec3::X, Vec3::ZERO)]);
+        assert_eq!(half_space.intersect_ray(&ray), Some((1.0, f32::INFINITY)));
+        assert_eq!(half_space.raycast(&ray, 10.0).unwrap().t, 1.0);
+        assert_eq!(half_space.raycast(&inside, 10.0), None);
+    }
+
+    #[test]
+    fn test_from_convex_hull_and_transform() {
+        let points = [Vec3::ZERO, Vec3::X, Vec3::Y, Vec3::Z, Vec3::splat(0.1)];
+        let hull = ConvexHull::from_points(&points).unwrap();
+        let tetrahedron = ConvexPolyhedron::from_convex_hull(&hull, &points);
+        assert!(tetrahedron.contains(Vec3::splat(0.2)));
+        assert!(!tetrahedron.contains(Vec3::splat(0.5)));
+        assert_eq!(tetrahedron.vertices().unwrap().len(), 4);
+        assert_eq!(tetrahedron.support(Vec3::Z), Some(Vec3::Z));
+        let vertices = tetrahedron.vertices().unwrap();
+        assert!(crate::gjk_intersects(vertices, &Vec3::splat(0.2)));
+        assert!(!crate::gjk_intersects(vertices, &Vec3::splat(0.5)));
+
+        let iso = IsoTransform::from_rotation_translation(
+            Quat::from_rotation_z(std::f32::consts::FRAC_PI_2),
+            Vec3::new(10.0, 0.0, 0.0),
+        );
+        let moved = tetrahedron.transform_iso(&iso);
+        assert!(moved.contains(Vec3::new(9.8, 0.2, 0.2)));
+        assert!(!moved.contains(Vec3::new(10.2, 0.2, 0.2)));
+        assert!(
+            moved
+                .support(Vec3::Y)
+                .unwrap()
+                .abs_diff_eq(Vec3::new(10.0, 1.0, 0.0), 1e-6)
+        );
+
+        let conformal = Conformal3::from_scale_rotation_translation(2.0, Quat::IDENTITY, Vec3::Y);
+        let scaled = tetrahedron.transform_conformal3(&conformal);
+        assert!(scaled.contains(Vec3::new(0.6, 1.6, 0.6)));
+        assert!(!scaled.contains(Vec3::new(0.6, 0.9, 0.6)));
+        assert_eq!(scaled.support(Vec3::X), Some(Vec3::new(2.0, 1.0, 0.0)));
+    }
+}
@@ -0,0 +1,388 @@
+use crate::BoundingBox;
+use crate::DIsoTransform;
+use crate::DRay3;
+use crate::DVec3;
+use crate::Vec3;
+
+/// A 3-dimensional axis-aligned bounding box with double precision.
+///
+/// The `f64` counterpart of [`BoundingBox`], useful for large worlds.
+#[derive(Clone, Copy, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "speedy", derive(speedy::Writable, speedy::Readable))]
+#[cfg_attr(feature = "speedy", allow(clippy::manual_slice_size_calculation))]
+pub struct DBoundingBox {
+    /// Bounding box minimum (inclusive).
+    pub min: DVec3,
+    /// Bounding box maximum (inclusive).
+    pub max: DVec3,
+}
+
+impl core::fmt::Debug for DBoundingBox {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{:?} - {:?}", self.min, self.max)
+    }
+}
+
+impl DBoundingBox {
+    /// A [`DBoundingBox`] that only contains [`DVec3::ZERO`].
+    pub const ZERO: Self = Self {
+        min: DVec3::ZERO,
+        max: DVec3::ZERO,
+    };
+
+    /// A [`DBoundingBox`] that contains no points.
+    ///
+    /// This is useful as the seed for bounding bounding boxes.
+    #[inline]
+    pub fn nothing() -> Self {
+        Self {
+            min: DVec3::splat(f64::INFINITY),
+            max: DVec3::splat(f64::NEG_INFINITY),
+        }
+    }
+
+    /// A [`DBoundingBox`] that contains every point.
+    #[inline]
+    pub fn everything() -> Self {
+        Self {
+            min: DVec3::splat(f64::NEG_INFINITY),
+            max: DVec3::splat(f64::INFINITY),
+        }
+    }
+
+    /// Create a bounding box from a minimum and maximum position.
+    #[inline]
+    pub fn from_min_max(min: DVec3, max: DVec3) -> Self {
+        Self { min, max }
+    }
+
+    /// Create a bounding box from a minimum position and a size.
+    #[inline]
+    pub fn from_min_size(min: DVec3, size: DVec3) -> Self {
+        Self {
+            min,
+            max: min + size,
+        }
+    }
+
+    /// Create a bounding box from a center position and a size.
+    pub fn from_center_size(center: DVec3, size: DVec3) -> Self {
+        Self::from_min_max(center - 0.5 * size, center + 0.5 * size)
+    }
+
+    /// Create a bounding box from an iterator of points that the bounding box will cover.
+    pub fn from_points(points: impl Iterator<Item = DVec3>) -> Self {
+        let mut bb = Self::nothing();
+        for p in points {
+            bb.extend(p);
+        }
+        bb
+    }
+
+    /// Convert a single precision bounding box, relative to `origin`, to double precision.
+    #[inline]
+    pub fn from_bounding_box_relative_to(bb: &BoundingBox, origin: DVec3) -> Self {
+        Self::from(*bb).translated(origin)
+    }
+
+    /// Convert to a single precision bounding box relative to `origin`.
+    ///
+    /// The result is rounded outwards so that it still contains every point this box does.
+    pub fn to_bounding_box_relative_to(&self, origin: DVec3) -> BoundingBox {
+        let min = self.min - origin;
+        let max = self.max - origin;
+        BoundingBox::from_min_max(
+            Vec3::new(round_down(min.x), round_down(min.y), round_down(min.z)),
+            Vec3::new(round_up(max.x), round_up(max.y), round_up(max.z)),
+        )
+    }
+
+    /// Returns the center point of the bounding box.
+    #[inline]
+    pub fn center(&self) -> DVec3 {
+        (self.min + self.max) * 0.5
+    }
+
+    /// Returns the 3D axis size of the bounding box.
+    #[inline]
+    pub fn size(&self) -> DVec3 {
+        self.max - self.min
+    }
+
+    /// Returns half the size (similar to a radius).
+    #[inline]
+    pub fn half_size(&self) -> DVec3 {
+        0.5 * (self.max - self.min)
+    }
+
+    /// Only correct for positively sized boxes.
+    pub fn volume(&self) -> f64 {
+        let s = self.size();
+        s.x * s.y * s.z
+    }
+
+    /// True if and only if there is at least one point for which `bb.contains(point)` is true.
+    ///
+    /// The opposite of `is_nothing()`.
+    pub fn is_something(&self) -> bool {
+        self.min.x <= self.max.x && self.min.y <= self.max.y && self.min.z <= self.max.z
+    }
+
+    /// True if and only if there is no point for which `bb.contains(point)` is true.
+    ///
+    /// The opposite of `is_something()`.
+    pub fn is_nothing(&self) -> bool {
+        self.max.x < self.min.x || self.max.y < self.min.y || self.max.z < self.min.z
+    }
+
+    /// True if this box contains exactly one point.
+    #[inline]
+    pub fn is_point(&self) -> bool {
+        self.min == self.max
+    }
+
+    /// Returns `true` if, and only if, all elements are finite.
+    #[inline]
+    pub fn is_finite(&self) -> bool {
+        self.min.is_finite() && self.max.is_finite()
+    }
+
+    /// Returns `true` if any elements are `NaN`.
+    #[inline]
+    pub fn is_nan(&self) -> bool {
+        self.min.is_nan() || self.max.is_nan()
+    }
+
+    /// The eight corners of this bounding box.
+    ///
+    /// Uses the same order as [`BoundingBox::corners`].
+    pub fn corners(&self) -> [DVec3; 8] {
+        [
+            self.min,
+            DVec3::new(self.min.x, self.min.y, self.max.z),
+            DVec3::new(self.min.x, self.max.y, self.min.z),
+            DVec3::new(self.min.x, self.max.y, self.max.z),
+            DVec3::new(self.max.x, self.min.y, self.min.z),
+            DVec3::new(self.max.x, self.min.y, self.max.z),
+            DVec3::new(self.max.x, self.max.y, self.min.z),
+            self.max,
+        ]
+    }
+
+    /// Enlarge the box to include this point.
+    #[inline]
+    pub fn extend(&mut self, pos: DVec3) {
+        self.min = self.min.min(pos);
+        self.max = self.max.max(pos);
+    }
+
+    /// Returns the smallest box that covers both `self` and `other`.
+    #[must_use]
+    pub fn union(mut self, other: Self) -> Self {
+        self.min = self.min.min(other.min);
+        self.max = self.max.max(other.max);
+        self
+    }
+
+    /// Returns the smallest volume that is covered by both `self` and `other`,
+    /// or [`Self::nothing`] if the boxes are disjoint.
+    #[must_use]
+    pub fn intersection(self, other: Self) -> Self {
+        let intersection = Self {
+            min: self.min.max(other.min),
+            max: self.max.min(other.max),
+        };
+        if intersection.is_nothing() {
+            Self::nothing()
+        } else {
+            intersection
+        }
+    }
+
+    /// Returns `true` if the point is within (or on the edge of) the box.
+    #[must_use]
+    pub fn contains(&self, point: DVec3) -> bool {
+        (self.min.x <= point.x && point.x <= self.max.x)
+            && (self.min.y <= point.y && point.y <= self.max.y)
+            && (self.min.z <= point.z && point.z <= self.max.z)
+    }
+
+    /// Expand with this much padding on each side.
+    #[must_use]
+    pub fn expanded(&self, padding: DVec3) -> Self {
+        Self {
+            min: self.min - padding,
+            max: self.max + padding,
+        }
+    }
+
+    /// Translate (move) the box by this much.
+    #[must_use]
+    pub fn translated(&self, translation: DVec3) -> Self {
+        Self {
+            min: self.min + translation,
+            max: self.max + translation,
+        }
+    }
+
+    /// Return a bounding box that contains this box after it has been transformed.
+    ///
+    /// Note that the rotated bounding box is very likely larger than the original,
+    /// since it must be large enough to contain the now rotated box.
+    #[must_use]
+    pub fn transform_iso(&self, m: &DIsoTransform) -> Self {
+        if self.is_nothing() {
+            return Self::nothing();
+        }
+
+        // Inspired by:
+        // https://zeux.io/2010/10/17/aabb-from-obb-with-component-wise-abs
+        let center = m.transform_point3(self.center());
+        let matrix3 = crate::DMat3::from_quat(m.rotation);
+        let abs_matrix3 = crate::DMat3::from_cols(
+            matrix3.x_axis.abs(),
+            matrix3.y_axis.abs(),
+            matrix3.z_axis.abs(),
+        );
+        let half_size = abs_matrix3.mul_vec3(self.half_size());
+
+        Self {
+            min: center - half_size,
+            max: center + half_size,
+        }
+    }
+
+    /// Intersects the box with a ray using the slab test.
+    ///
+    /// Returns the distances `(t_enter, t_exit)` along the ray where it enters and exits the box,
+    /// or [`None`] if the ray misses the box or the box is behind the ray.
+    /// `t_enter` is negative if the ray starts inside the box.
+    pub fn intersect_ray(&self, ray: &DRay3) -> Option<(f64, f64)> {
+        if self.is_nothing() {
+            return None;
+        }
+
+        let inv_dir = ray.dir.recip();
+        let mut t_enter = f64::NEG_INFINITY;
+        let mut t_exit = f64::INFINITY;
+
+        for i in 0..3 {
+            if inv_dir[i].is_infinite() {
+                // Parallel to the slab, so we never enter or exit it.
+                if ray.origin[i] < self.min[i] || self.max[i] < ray.origin[i] {
+                    return None;
+                }
+            } else {
+                let t0 = (self.min[i] - ray.origin[i]) * inv_dir[i];
+                let t1 = (self.max[i] - ray.origin[i]) * inv_dir[i];
+                t_enter = t_enter.max(t0.min(t1));
+                t_exit = t_exit.min(t0.max(t1));
+            }
+        }
+
+        (t_enter <= t_exit && 0.0 <= t_exit).then_some((t_enter, t_exit))
+    }
+}
+
+impl From<BoundingBox> for DBoundingBox {
+    #[inline]
+    fn from(bb: BoundingBox) -> Self {
+        Self::from_min_max(bb.min.as_dvec3(), bb.max.as_dvec3())
+    }
+}
+
+/// The largest `f32` that is smaller than or equal to `v`.
+fn round_down(v: f64) -> f32 {
+    let f = v as f32;
+    if v < f64::from(f) { next_down(f) } else { f }
+}
+
+/// The smallest `f32` that is larger than or equal to `v`.
+fn round_up(v: f64) -> f32 {
+    let f = v as f32;
+    if f64::from(f) < v { -next_down(-f) } else { f }
+}
+
+fn next_down(f: f32) -> f32 {
+    if f.is_nan() || f == f32::NEG_INFINITY {
+        f
+    } else if f == 0.0 {
+        -f32::from_bits(1)
+    } else if f < 0.0 {
+        f32::from_bits(f.to_bits() + 1)
+    } else {
+        f32::from_bits(f.to_bits() - 1)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_relative_conversion() {
+        let origin = DVec3::new(1e9, -2e9, 3e7);
+        let bb = DBoundingBox::from_min_max(
+            origin + DVec3::new(0.1, 0.2, 0.3),
+            origin + DVec3::new(1.1, 1.2, 1.3),
+        );
+
+        let local = bb.to_bounding_box_relative_to(origin);
+        assert!(local.min.abs_diff_eq(Vec3::new(0.1, 0.2, 0.3), 1e-6));
+        assert!(local.max.abs_diff_eq(Vec3::new(1.1, 1.2, 1.3), 1e-6));
+
+        // Rounding outwards keeps the converted box conservative.
+        let back = DBoundingBox::from_bounding_box_relative_to(&local, origin);
+        assert!(back.contains(bb.min) && back.contains(bb.max));
+
+        assert!(
+            DBoundingBox::nothing()
+                .to_bounding_box_relative_to(origin)
+                .is_nothing()
+        );
+    }
+
+    #[test]
+    fn test_intersect_ray() {
+        #![allow(clippy::float_cmp)]
+        let origin = DVec3::new(1e9, -2e9, 3e7);
+        let bb = DBoundingBox::from_min_max(origin, origin + DVec3::ONE);
+
+        let ray = DRay3::from_origin_dir(origin + DVec3::new(-1.0, 0.5, 0.5), DVec3::X);
+        assert_eq!(bb.intersect_ray(&ray), Some((1.0, 2.0)));
+
+        let miss = DRay3::from_origin_dir(origin + DVec3::new(-1.0, 2.0, 0.5), DVec3::X);
+        assert_eq!(bb.intersect_ray(&miss), None);
+        let behind = DRay3::from_origin_dir(ray.origin, -DVec3::X);
+        assert_eq!(bb.intersect_ray(&behind), None);
+
+        // From inside, we enter behind the origin.
+        let inside = DRay3::from_origin_dir(origin + DVec3::splat(0.5), DVec3::Y);
+        assert_eq!(bb.intersect_ray(&inside), Some((-0.5, 0.5)));
+
+        // Parallel to the Z slab, passing through it and above it.
+        let diagonal = DRay3::from_origin_dir(
+            origin + DVec3::new(-1.0, -1.0, 0.5),
+            DVec3::new(1.0, 1.0, 0.0).normalize(),
+        );
+        let (t_enter, t_exit) = bb.intersect_ray(&diagonal).unwrap();
+        assert!((t_enter - 2.0_f64.sqrt()).abs() < 1e-6);
+        assert!((t_exit - 2.0 * 2.0_f64.sqrt()).abs() < 1e-6);
+        let above = DRay3::from_origin_dir(diagonal.origin + DVec3::Z, diagonal.dir);
+        assert_eq!(bb.intersect_ray(&above), None);
+
+        assert_eq!(DBoundingBox::nothing().intersect_ray(&ray), None);
+    }
+
+    #[test]
+    fn test_rounding() {
+        #![allow(clippy::float_cmp)]
+        for v in [0.1, -0.1, 0.0, 1e-50, -1e-50, 3.0, 1e300, -1e300] {
+            assert!(f64::from(round_down(v)) <= v, "{v}");
+            assert!(v <= f64::from(round_up(v)), "{v}");
+        }
+        assert_eq!(round_down(3.0), 3.0);
+        assert_eq!(round_up(3.0), 3.0);
+    }
+}
@@ -0,0 +1,256 @@
+use crate::DMat4;
+use crate::DQuat;
+use crate::DVec3;
+use crate::IsoTransform;
+
+/// An isometric transform with double precision, represented by translation * rotation.
+///
+/// The `f64` counterpart of [`IsoTransform`], useful for large worlds.
+///
+/// The operations are applied right-to-left, so when transforming a point
+/// it will first be rotated and finally translated.
+#[derive(Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "speedy", derive(speedy::Writable, speedy::Readable))]
+pub struct DIsoTransform {
+    /// Normalized
+    pub rotation: DQuat,
+
+    /// Final translation. This is where the input origin will end up,
+    /// so for many circumstances this can be thought of as the position.
+    pub translation: DVec3,
+}
+
+/// Identity transform
+impl Default for DIsoTransform {
+    /// Identity transform
+    #[inline]
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
+
+impl DIsoTransform {
+    // ------------------------------------------------------------------------
+    // Constructors:
+
+    /// The identity transform: doesn't transform at all. Like multiplying with `1`.
+    pub const IDENTITY: Self = Self {
+        rotation: DQuat::IDENTITY,
+        translation: DVec3::ZERO,
+    };
+
+    /// A transform that first rotates around the origin and then moves all points by a set amount.
+    ///
+    /// The given rotation should be normalized.
+    #[inline]
+    pub fn from_rotation_translation(rotation: DQuat, translation: DVec3) -> Self {
+        Self {
+            rotation,
+            translation,
+        }
+    }
+
+    /// A pure rotation without any translation.
+    ///
+    /// The given rotation should be normalized.
+    #[inline]
+    pub fn from_quat(rotation: DQuat) -> Self {
+        Self {
+            rotation,
+            translation: DVec3::ZERO,
+        }
+    }
+
+    /// A pure translation without any rotation.
+    #[inline]
+    pub fn from_translation(translation: DVec3) -> Self {
+        Self {
+            rotation: DQuat::IDENTITY,
+            translation,
+        }
+    }
+
+    /// Convert a single precision transform, relative to `origin`, to double precision.
+    ///
+    /// The result transforms points into the space `origin` is given in.
+    #[inline]
+    pub fn from_iso_transform_relative_to(iso: &IsoTransform, origin: DVec3) -> Self {
+        Self {
+            rotation: iso.rotation.as_dquat(),
+            translation: iso.translation().as_dvec3() + origin,
+        }
+    }
+
+    /// Convert to a single precision transform relative to `origin`.
+    ///
+    /// The result transforms points into a space where `origin` is at zero.
+    #[inline]
+    pub fn to_iso_transform_relative_to(&self, origin: DVec3) -> IsoTransform {
+        IsoTransform::from_rotation_translation(
+            self.rotation.as_quat(),
+            (self.translation - origin).as_vec3(),
+        )
+    }
+
+    // ------------------------------------------------------------------------
+    // Accessors:
+
+    #[inline]
+    pub fn rotation(&self) -> DQuat {
+        self.rotation
+    }
+
+    #[inline]
+    pub fn translation(&self) -> DVec3 {
+        self.translation
+    }
+
+    /// True if every value is finite
+    #[inline]
+    pub fn is_finite(&self) -> bool {
+        self.translation.is_finite() && self.rotation.is_finite()
+    }
+
+    /// Returns `true` if any elements are `NaN`.
+    #[inline]
+    pub fn is_nan(&self) -> bool {
+        self.translation.is_nan() || self.rotation.is_nan()
+    }
+
+    // ------------------------------------------------------------------------
+    // Conversions:
+
+    /// Convert to an equivalent `DMat4` transformation matrix.
+    #[inline]
+    pub fn to_dmat4(self) -> DMat4 {
+        DMat4::from_rotation_translation(self.rotation, self.translation)
+    }
+
+    // ------------------------------------------------------------------------
+    // Operations:
+
+    /// Get the transform that undoes this transform so that `t.inverse() * t == IDENTITY`.
+    #[inline]
+    #[must_use]
+    pub fn inverse(&self) -> Self {
+        let inv_rotation = self.rotation.inverse();
+        Self {
+            rotation: inv_rotation,
+            translation: -(inv_rotation * self.translation),
+        }
+    }
+
+    /// Returns self normalized.
+    #[inline]
+    #[must_use]
+    pub fn normalize(&self) -> Self {
+        Self {
+            rotation: self.rotation.normalize(),
+            translation: self.translation,
+        }
+    }
+
+    /// Rotate and translate a point.
+    #[inline]
+    pub fn transform_point3(&self, p: DVec3) -> DVec3 {
+        self.translation + self.rotation * p
+    }
+
+    /// Rotate a vector.
+    #[inline]
+    pub fn transform_vector3(&self, v: DVec3) -> DVec3 {
+        self.rotation * v
+    }
+}
+
+/// iso * iso -> iso
+impl core::ops::Mul for DIsoTransform {
+    type Output = Self;
+
+    #[inline]
+    fn mul(self, rhs: Self) -> Self {
+        Self {
+            rotation: self.rotation * rhs.rotation,
+            translation: self.translation + self.rotation * rhs.translation,
+        }
+    }
+}
+
+impl From<IsoTransform> for DIsoTransform {
+    #[inline]
+    fn from(iso: IsoTransform) -> Self {
+        Self::from_rotation_translation(iso.rotation.as_dquat(), iso.translation().as_dvec3())
+    }
+}
+
+impl From<DIsoTransform> for DMat4 {
+    #[inline]
+    fn from(t: DIsoTransform) -> Self {
+        t.to_dmat4()
+    }
+}
+
+impl core::fmt::Debug for DIsoTransform {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let (axis, angle) = self.rotation.to_axis_angle();
+        f.debug_struct("DIsoTransform")
+            .field(
+                "translation",
+                &format!(
+                    "[{} {} {}]",
+                    self.translation[0], self.translation[1], self.translation[2]
+                ),
+            )
+            .field(
+                "rotation",
+                &format!(
+                    "{:.1}° around [{} {} {}]",
+                    angle.to_degrees(),
+                    axis[0],
+                    axis[1],
+                    axis[2],
+                ),
+            )
+            .field("rotation(raw)", &self.rotation)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::DRay3;
+    use crate::Quat;
+    use crate::Vec3;
+
+    #[test]
+    fn test_relative_conversion() {
+        let origin = DVec3::new(1e9, 2e8, -3e9);
+        let rotation = Quat::from_axis_angle(Vec3::new(1.0, 2.0, 3.0).normalize(), 1.2);
+        let iso = DIsoTransform::from_rotation_translation(
+            rotation.as_dquat(),
+            origin + DVec3::new(1.0, 2.0, 3.0),
+        );
+
+        let local = iso.to_iso_transform_relative_to(origin);
+        let p = Vec3::new(0.5, -0.25, 4.0);
+        let world = iso.transform_point3(p.as_dvec3());
+        assert!(
+            local
+                .transform_point3(p)
+                .abs_diff_eq((world - origin).as_vec3(), 1e-5)
+        );
+
+        let back = DIsoTransform::from_iso_transform_relative_to(&local, origin);
+        assert!(back.transform_point3(p.as_dvec3()).abs_diff_eq(world, 1e-5));
+
+        let ray = iso * DRay3::from_origin_dir(DVec3::ZERO, DVec3::X);
+        assert!(ray.origin.abs_diff_eq(iso.translation, 1e-9));
+        assert!(
+            iso.inverse()
+                .transform_point3(world)
+                .abs_diff_eq(p.as_dvec3(), 1e-6)
+        );
+    }
+}
@@ -0,0 +1,140 @@
+use crate::DVec3;
+use crate::Plane3;
+
+/// A 3-dimensional plane primitive with double precision.
+///
+/// The `f64` counterpart of [`Plane3`], useful for large worlds.
+///
+/// A point `point` is on the plane when `plane.normal.dot(point) + plane.d = 0`.
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "speedy", derive(speedy::Writable, speedy::Readable))]
+pub struct DPlane3 {
+    /// Normal vector
+    pub normal: DVec3,
+    /// Distance
+    pub d: f64,
+}
+
+impl DPlane3 {
+    /// The X^Y plane with normal = +Z
+    pub const XY: Self = Self {
+        normal: DVec3::Z,
+        d: 0.0,
+    };
+    /// The Y^Z plane with normal = +X
+    pub const YZ: Self = Self {
+        normal: DVec3::X,
+        d: 0.0,
+    };
+    /// The Z^X plane with normal = +Y
+    pub const ZX: Self = Self {
+        normal: DVec3::Y,
+        d: 0.0,
+    };
+
+    /// From the plane normal and a distance `d` so that for all points on the plane:
+    /// `normal.dot(point) + d = 0`.
+    #[inline]
+    pub fn from_normal_dist(normal: DVec3, d: f64) -> Self {
+        Self { normal, d }
+    }
+
+    /// From the plane normal and a point on the plane.
+    #[inline]
+    pub fn from_normal_point(normal: DVec3, point: DVec3) -> Self {
+        Self {
+            normal,
+            d: -normal.dot(point),
+        }
+    }
+
+    /// Convert a single precision plane, relative to `origin`, to double precision.
+    #[inline]
+    pub fn from_plane3_relative_to(plane: &Plane3, origin: DVec3) -> Self {
+        let normal = plane.normal.as_dvec3();
+        Self::from_normal_dist(normal, f64::from(plane.d) - normal.dot(origin))
+    }
+
+    /// Convert to a single precision plane relative to `origin`.
+    #[inline]
+    pub fn to_plane3_relative_to(&self, origin: DVec3) -> Plane3 {
+        Plane3::from_normal_dist(
+            self.normal.as_vec3(),
+            (self.d + self.normal.dot(origin)) as f32,
+        )
+    }
+
+    /// Get normalized plane
+    #[inline]
+    #[must_use]
+    pub fn normalized(&self) -> Self {
+        let inv_len = self.normal.length_recip();
+        Self {
+            normal: self.normal * inv_len,
+            d: self.d * inv_len,
+        }
+    }
+
+    /// Computes the distance between the plane and the point p.
+    /// The returned distance is only correct if the plane is normalized or the distance is zero.
+    #[inline]
+    pub fn distance(&self, p: DVec3) -> f64 {
+        self.normal.dot(p) + self.d
+    }
+
+    /// The bool is whether the plane was hit or not.
+    ///
+    /// If false, the ray was either perpendicular to the plane, or the ray shot away from the plane.
+    ///
+    /// The returned f64 is the t value so you can easily compute the
+    /// intersection point through "origin + dir * t".
+    pub fn intersect_ray(&self, origin: DVec3, dir: DVec3) -> (bool, f64) {
+        let denom = dir.dot(self.normal);
+        if denom == 0.0 {
+            (false, 0.0)
+        } else {
+            let t = -(origin.dot(self.normal) + self.d) / denom;
+            if t < 0.0 { (false, t) } else { (true, t) }
+        }
+    }
+
+    /// True if every value is finite
+    #[inline]
+    pub fn is_finite(&self) -> bool {
+        self.normal.is_finite() && self.d.is_finite()
+    }
+
+    /// The distance to a point `[x, y, z, 1]` is the dot product of the point and this.
+    #[inline]
+    pub fn as_dvec4(&self) -> crate::DVec4 {
+        self.normal.extend(self.d)
+    }
+}
+
+impl From<Plane3> for DPlane3 {
+    #[inline]
+    fn from(plane: Plane3) -> Self {
+        Self::from_normal_dist(plane.normal.as_dvec3(), f64::from(plane.d))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::Vec3;
+
+    #[test]
+    fn test_relative_conversion() {
+        let origin = DVec3::new(1e9, 2e8, -3e9);
+        let point = origin + DVec3::new(1.0, 2.0, 3.0);
+        let plane = DPlane3::from_normal_point(DVec3::new(1.0, 2.0, -2.0).normalize(), point);
+
+        let local = plane.to_plane3_relative_to(origin);
+        assert!(local.distance(Vec3::new(1.0, 2.0, 3.0)).abs() < 1e-5);
+        assert!((local.distance(Vec3::ZERO) - plane.distance(origin) as f32).abs() < 1e-5);
+
+        let back = DPlane3::from_plane3_relative_to(&local, origin);
+        assert!(back.distance(point).abs() < 1e-5);
+    }
+}
@@ -0,0 +1,116 @@
+use crate::DIsoTransform;
+use crate::DPlane3;
+use crate::DVec3;
+use crate::Ray3;
+
+/// A ray in 3-dimensional space with double precision.
+///
+/// The `f64` counterpart of [`Ray3`], useful for large worlds.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "speedy", derive(speedy::Writable, speedy::Readable))]
+pub struct DRay3 {
+    /// Start of the ray
+    pub origin: DVec3,
+    /// Direction of the ray, normalized
+    pub dir: DVec3,
+}
+
+impl DRay3 {
+    /// An invalid ray, starting at the origin and going nowhere.
+    pub const ZERO: Self = Self {
+        origin: DVec3::ZERO,
+        dir: DVec3::ZERO,
+    };
+
+    /// `dir` should be normalized
+    #[inline]
+    pub fn from_origin_dir(origin: DVec3, dir: DVec3) -> Self {
+        Self { origin, dir }
+    }
+
+    /// Convert a single precision ray, relative to `origin`, to double precision.
+    #[inline]
+    pub fn from_ray3_relative_to(ray: &Ray3, origin: DVec3) -> Self {
+        Self {
+            origin: ray.origin.as_dvec3() + origin,
+            dir: ray.dir.as_dvec3(),
+        }
+    }
+
+    /// Convert to a single precision ray relative to `origin`.
+    #[inline]
+    pub fn to_ray3_relative_to(&self, origin: DVec3) -> Ray3 {
+        Ray3::from_origin_dir((self.origin - origin).as_vec3(), self.dir.as_vec3())
+    }
+
+    /// Get normalized ray (where `dir.len() == 1`).
+    #[inline]
+    #[must_use]
+    pub fn normalize(&self) -> Self {
+        Self {
+            origin: self.origin,
+            dir: self.dir.normalize(),
+        }
+    }
+
+    /// Returns a new ray that has had its origin moved a given distance forwards along the ray.
+    #[inline]
+    #[must_use]
+    pub fn offset_along_ray(&self, t: f64) -> Self {
+        Self {
+            origin: self.origin + self.dir * t,
+            dir: self.dir,
+        }
+    }
+
+    /// True if every value is finite
+    #[inline]
+    pub fn is_finite(&self) -> bool {
+        self.origin.is_finite() && self.dir.is_finite()
+    }
+
+    #[inline]
+    pub fn point_along(&self, t: f64) -> DVec3 {
+        self.origin + t * self.dir
+    }
+
+    /// Returns the point where the ray intersects the plane.
+    /// Returns non-finite result of the ray and plane are parallel.
+    pub fn intersects_plane(&self, plane: DPlane3) -> DVec3 {
+        let t = -(self.origin.dot(plane.normal) + plane.d) / self.dir.dot(plane.normal);
+        self.point_along(t)
+    }
+
+    /// Returns the distance along the ray that is closest to the given point.
+    /// The returned `t` can be negative.
+    #[inline]
+    pub fn closest_t_to_point(&self, point: DVec3) -> f64 {
+        self.dir.dot(point - self.origin)
+    }
+
+    /// Returns the point along the ray that is closest to the given point.
+    /// The returned point may be "behind" the ray origin.
+    #[inline]
+    pub fn closest_point_to_point(&self, point: DVec3) -> DVec3 {
+        self.origin + self.dir * self.dir.dot(point - self.origin)
+    }
+}
+
+impl From<Ray3> for DRay3 {
+    #[inline]
+    fn from(ray: Ray3) -> Self {
+        Self::from_origin_dir(ray.origin.as_dvec3(), ray.dir.as_dvec3())
+    }
+}
+
+impl core::ops::Mul<DRay3> for DIsoTransform {
+    type Output = DRay3;
+
+    fn mul(self, rhs: DRay3) -> DRay3 {
+        DRay3 {
+            origin: self.transform_point3(rhs.origin),
+            dir: self.transform_vector3(rhs.dir),
+        }
+    }
+}
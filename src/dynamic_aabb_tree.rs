@@ -0,0 +1,645 @@
+use crate::BoundingBox;
+use crate::Ray3;
+use crate::Vec3;
+
+const NULL: u32 = u32::MAX;
+
+/// A handle to a leaf in a [`DynamicAabbTree`].
+///
+/// Handles are reused after the leaf they refer to has been removed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct AabbTreeHandle(u32);
+
+impl AabbTreeHandle {
+    /// The index of the node this handle refers to.
+    #[inline]
+    pub fn index(self) -> usize {
+        self.0 as usize
+    }
+}
+
+#[derive(Clone, Debug)]
+struct Node<T> {
+    /// For leaves, the fattened bounds of the user box.
+    bounds: BoundingBox,
+    /// Parent node, or the next free node if this node is free.
+    parent: u32,
+    /// Both are [`NULL`] for leaves.
+    children: [u32; 2],
+    /// Leaves have height 0, free nodes -1.
+    height: i32,
+    /// Only set for leaves.
+    data: Option<T>,
+}
+
+impl<T> Node<T> {
+    #[inline]
+    fn is_leaf(&self) -> bool {
+        self.children[0] == NULL
+    }
+}
+
+/// A dynamic bounding volume hierarchy for moving objects, such as in a broadphase.
+///
+/// Each leaf stores a fattened [`BoundingBox`] (the user box expanded by a margin) and some
+/// user data. Moving a box only reinserts its leaf once the box leaves its fattened bounds.
+/// The tree is kept balanced using rotations when leaves are inserted and removed.
+///
+/// Based on the dynamic tree in [Box2D](https://github.com/erincatto/box2d).
+#[derive(Clone, Debug)]
+pub struct DynamicAabbTree<T> {
+    nodes: Vec<Node<T>>,
+    root: u32,
+    free_list: u32,
+    margin: Vec3,
+    leaf_count: usize,
+}
+
+impl<T> Default for DynamicAabbTree<T> {
+    fn default() -> Self {
+        Self::new(Self::DEFAULT_MARGIN)
+    }
+}
+
+impl<T> DynamicAabbTree<T> {
+    /// The default padding added to each side of the user boxes.
+    pub const DEFAULT_MARGIN: f32 = 0.1;
+
+    /// Create an empty tree where leaves are fattened with `margin` on each side.
+    pub fn new(margin: f32) -> Self {
+        Self {
+            nodes: Vec::new(),
+            root: NULL,
+            free_list: NULL,
+            margin: Vec3::splat(margin),
+            leaf_count: 0,
+        }
+    }
+
+    /// The number of leaves in the tree.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.leaf_count
+    }
+
+    /// True if the tree has no leaves.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.leaf_count == 0
+    }
+
+    /// The height of the tree, where a tree with a single leaf has height 0.
+    ///
+    /// Returns 0 for an empty tree.
+    pub fn height(&self) -> usize {
+        if self.root == NULL {
+            0
+        } else {
+            self.nodes[self.root as usize].height as usize
+        }
+    }
+
+    /// The bounds of all fattened leaves, or [`BoundingBox::nothing`] if the tree is empty.
+    pub fn bounds(&self) -> BoundingBox {
+        if self.root == NULL {
+            BoundingBox::nothing()
+        } else {
+            self.nodes[self.root as usize].bounds
+        }
+    }
+
+    /// Insert a new leaf.
+    pub fn insert(&mut self, bb: BoundingBox, data: T) -> AabbTreeHandle {
+        let leaf = self.allocate_node(bb.expanded(self.margin), Some(data));
+        self.insert_leaf(leaf);
+        self.leaf_count += 1;
+        AabbTreeHandle(leaf)
+    }
+
+    /// Remove a leaf, returning its data.
+    ///
+    /// Returns [`None`] if the handle does not refer to a leaf.
+    pub fn remove(&mut self, handle: AabbTreeHandle) -> Option<T> {
+        let node = self.nodes.get(handle.index())?;
+        if node.height != 0 {
+            return None;
+        }
+
+        self.remove_leaf(handle.0);
+        self.leaf_count -= 1;
+        let data = self.nodes[handle.index()].data.take();
+        self.free_node(handle.0);
+        data
+    }
+
+    /// Move a leaf to a new box.
+    ///
+    /// The leaf is only reinserted if `bb` is not contained within its fattened bounds.
+    /// Returns `true` if the leaf was reinserted.
+    ///
+    /// # Panics
+    ///
+    /// If the handle does not refer to a leaf.
+    pub fn update(&mut self, handle: AabbTreeHandle, bb: BoundingBox) -> bool {
+        let node = &self.nodes[handle.index()];
+        assert!(node.height == 0, "Not a leaf: {handle:?}");
+
+        let fat = node.bounds;
+        if fat.contains(bb.min) && fat.contains(bb.max) {
+            return false;
+        }
+
+        self.remove_leaf(handle.0);
+        self.nodes[handle.index()].bounds = bb.expanded(self.margin);
+        self.insert_leaf(handle.0);
+        true
+    }
+
+    /// The data of a leaf.
+    pub fn get(&self, handle: AabbTreeHandle) -> Option<&T> {
+        self.nodes.get(handle.index())?.data.as_ref()
+    }
+
+    /// The data of a leaf.
+    pub fn get_mut(&mut self, handle: AabbTreeHandle) -> Option<&mut T> {
+        self.nodes.get_mut(handle.index())?.data.as_mut()
+    }
+
+    /// The fattened bounds of a leaf.
+    pub fn fat_bounds(&self, handle: AabbTreeHandle) -> Option<BoundingBox> {
+        let node = self.nodes.get(handle.index())?;
+        (node.height == 0).then_some(node.bounds)
+    }
+
+    /// Call `callback` for every leaf whose fattened bounds overlap (or touch) `bb`.
+    pub fn for_each_intersecting(
+        &self,
+        bb: &BoundingBox,
+        callback: impl FnMut(AabbTreeHandle, &T),
+    ) {
+        self.for_each_in_nodes(|bounds| bounds.intersects(bb), callback);
+    }
+
+    /// Call `callback` for every leaf whose fattened bounds contain `point`.
+    pub fn for_each_containing(&self, point: Vec3, callback: impl FnMut(AabbTreeHandle, &T)) {
+        self.for_each_in_nodes(|bounds| bounds.contains(point), callback);
+    }
+
+    /// Call `callback` for every leaf whose fattened bounds are hit by the ray
+    /// within the range `[0, max_t]`.
+    pub fn for_each_ray_hit(
+        &self,
+        ray: &Ray3,
+        max_t: f32,
+        callback: impl FnMut(AabbTreeHandle, &T),
+    ) {
+        let inv_dir = ray.inv_dir();
+        self.for_each_in_nodes(
+            |bounds| {
+                bounds
+                    .intersect_ray_inv_dir(ray.origin, inv_dir)
+                    .is_some_and(|(t_enter, _)| t_enter <= max_t)
+            },
+            callback,
+        );
+    }
+
+    /// Find the nearest leaf hit by the ray.
+    ///
+    /// `intersect` is called for leaves whose fattened bounds are hit, and should return the
+    /// distance along the ray to the actual object, or [`None`] if it was missed.
+    ///
+    /// Returns the nearest leaf and its distance, considering only hits in the range `[0, max_t]`.
+    pub fn nearest_ray_hit(
+        &self,
+        ray: &Ray3,
+        max_t: f32,
+        mut intersect: impl FnMut(AabbTreeHandle, &T) -> Option<f32>,
+    ) -> Option<(AabbTreeHandle, f32)> {
+        let inv_dir = ray.inv_dir();
+        let mut nearest = None;
+        let mut nearest_t = max_t;
+
+        let mut stack = Vec::with_capacity(64);
+        if self.root != NULL {
+            stack.push(self.root);
+        }
+
+        while let Some(index) = stack.pop() {
+            let node = &self.nodes[index as usize];
+            let hit = node.bounds.intersect_ray_inv_dir(ray.origin, inv_dir);
+            if !hit.is_some_and(|(t_enter, _)| t_enter <= nearest_t) {
+                continue;
+            }
+
+            if let Some(data) = &node.data {
+                let t = intersect(AabbTreeHandle(index), data)
+                    .filter(|t| (0.0..=nearest_t).contains(t));
+                if let Some(t) = t {
+                    nearest = Some((AabbTreeHandle(index), t));
+                    nearest_t = t;
+                }
+            } else {
+                stack.extend(node.children);
+            }
+        }
+
+        nearest
+    }
+
+    /// Call `callback` once for every pair of leaves whose fattened bounds overlap.
+    ///
+    /// The first handle of each pair is always smaller than the second.
+    pub fn for_each_pair(&self, mut callback: impl FnMut(AabbTreeHandle, AabbTreeHandle)) {
+        for (index, node) in self.nodes.iter().enumerate() {
+            if node.height != 0 {
+                continue;
+            }
+            let handle = AabbTreeHandle(index as u32);
+            self.for_each_intersecting(&node.bounds, |other, _| {
+                if handle < other {
+                    callback(handle, other);
+                }
+            });
+        }
+    }
+
+    fn for_each_in_nodes(
+        &self,
+        mut test: impl FnMut(&BoundingBox) -> bool,
+        mut callback: impl FnMut(AabbTreeHandle, &T),
+    ) {
+        let mut stack = Vec::with_capacity(64);
+        if self.root != NULL {
+            stack.push(self.root);
+        }
+
+        while let Some(index) = stack.pop() {
+            let node = &self.nodes[index as usize];
+            if !test(&node.bounds) {
+                continue;
+            }
+
+            if let Some(data) = &node.data {
+                callback(AabbTreeHandle(index), data);
+            } else {
+                stack.extend(node.children);
+            }
+        }
+    }
+
+    fn allocate_node(&mut self, bounds: BoundingBox, data: Option<T>) -> u32 {
+        let node = Node {
+            bounds,
+            parent: NULL,
+            children: [NULL; 2],
+            height: 0,
+            data,
+        };
+
+        if self.free_list == NULL {
+            self.nodes.push(node);
+            (self.nodes.len() - 1) as u32
+        } else {
+            let index = self.free_list;
+            self.free_list = self.nodes[index as usize].parent;
+            self.nodes[index as usize] = node;
+            index
+        }
+    }
+
+    fn free_node(&mut self, index: u32) {
+        let node = &mut self.nodes[index as usize];
+        node.parent = self.free_list;
+        node.children = [NULL; 2];
+        node.height = -1;
+        node.data = None;
+        self.free_list = index;
+    }
+
+    fn insert_leaf(&mut self, leaf: u32) {
+        if self.root == NULL {
+            self.root = leaf;
+            self.nodes[leaf as usize].parent = NULL;
+            return;
+        }
+
+        // Find the best sibling by descending towards the child with the lowest cost.
+        let leaf_bounds = self.nodes[leaf as usize].bounds;
+        let mut index = self.root;
+        while !self.nodes[index as usize].is_leaf() {
+            let node = &self.nodes[index as usize];
+            let area = node.bounds.surface_area();
+            let combined_area = node.bounds.union(leaf_bounds).surface_area();
+
+            // Cost of creating a new parent for this node and the new leaf.
+            let cost = 2.0 * combined_area;
+            // Minimum cost of pushing the leaf further down the tree.
+            let inheritance_cost = 2.0 * (combined_area - area);
+
+            let child_cost = |child: u32| {
+                let child = &self.nodes[child as usize];
+                let combined_area = child.bounds.union(leaf_bounds).surface_area();
+                if child.is_leaf() {
+                    combined_area + inheritance_cost
+                } else {
+                    combined_area - child.bounds.surface_area() + inheritance_cost
+                }
+            };
+
+            let [child0, child1] = node.children;
+            let cost0 = child_cost(child0);
+            let cost1 = child_cost(child1);
+
+            if cost < cost0 && cost < cost1 {
+                break;
+            }
+            index = if cost0 < cost1 { child0 } else { child1 };
+        }
+        let sibling = index;
+
+        // Create a new parent for the sibling and the leaf.
+        let old_parent = self.nodes[sibling as usize].parent;
+        let new_parent =
+            self.allocate_node(leaf_bounds.union(self.nodes[sibling as usize].bounds), None);
+        let height = self.nodes[sibling as usize].height + 1;
+        {
+            let node = &mut self.nodes[new_parent as usize];
+            node.parent = old_parent;
+            node.children = [sibling, leaf];
+            node.height = height;
+        }
+        self.nodes[sibling as usize].parent = new_parent;
+        self.nodes[leaf as usize].parent = new_parent;
+
+        if old_parent == NULL {
+            self.root = new_parent;
+        } else {
+            self.replace_child(old_parent, sibling, new_parent);
+        }
+
+        self.refit_ancestors(new_parent);
+    }
+
+    fn remove_leaf(&mut self, leaf: u32) {
+        if leaf == self.root {
+            self.root = NULL;
+            return;
+        }
+
+        let parent = self.nodes[leaf as usize].parent;
+        let grand_parent = self.nodes[parent as usize].parent;
+        let [child0, child1] = self.nodes[parent as usize].children;
+        let sibling = if child0 == leaf { child1 } else { child0 };
+
+        // Replace the parent with the sibling.
+        self.nodes[sibling as usize].parent = grand_parent;
+        if grand_parent == NULL {
+            self.root = sibling;
+        } else {
+            self.replace_child(grand_parent, parent, sibling);
+        }
+        self.free_node(parent);
+
+        if grand_parent != NULL {
+            self.refit_ancestors(grand_parent);
+        }
+    }
+
+    fn replace_child(&mut self, parent: u32, old_child: u32, new_child: u32) {
+        let children = &mut self.nodes[parent as usize].children;
+        if children[0] == old_child {
+            children[0] = new_child;
+        } else {
+            children[1] = new_child;
+        }
+    }
+
+    /// Rebalance and recompute bounds and heights from `index` up to the root.
+    fn refit_ancestors(&mut self, mut index: u32) {
+        while index != NULL {
+            index = self.balance(index);
+            let [child0, child1] = self.nodes[index as usize].children;
+            let (c0, c1) = (&self.nodes[child0 as usize], &self.nodes[child1 as usize]);
+            let height = 1 + c0.height.max(c1.height);
+            let bounds = c0.bounds.union(c1.bounds);
+
+            let node = &mut self.nodes[index as usize];
+            node.height = height;
+            node.bounds = bounds;
+            index = node.parent;
+        }
+    }
+
+    /// If `a` is imbalanced, rotate its taller child up. Returns the new root of the subtree.
+    fn balance(&mut self, a: u32) -> u32 {
+        let node_a = &self.nodes[a as usize];
+        if node_a.is_leaf() || node_a.height < 2 {
+            return a;
+        }
+
+        let [b, c] = node_a.children;
+        let balance = self.nodes[c as usize].height - self.nodes[b as usize].height;
+
+        if balance > 1 {
+            self.rotate_up(a, c, 1)
+        } else if balance < -1 {
+            self.rotate_up(a, b, 0)
+        } else {
+            a
+        }
+    }
+
+    /// Rotate `child` (which is `a.children[side]`) up to replace `a`.
+    ///
+    /// The taller grandchild stays under `child`, while the shorter one moves under `a`.
+    fn rotate_up(&mut self, a: u32, child: u32, side: usize) -> u32 {
+        let [f, g] = self.nodes[child as usize].children;
+        let a_parent = self.nodes[a as usize].parent;
+
+        // `child` takes the place of `a`.
+        self.nodes[child as usize].parent = a_parent;
+        self.nodes[a as usize].parent = child;
+        if a_parent == NULL {
+            self.root = child;
+        } else {
+            self.replace_child(a_parent, a, child);
+        }
+
+        let (taller, shorter) = if self.nodes[f as usize].height > self.nodes[g as usize].height {
+            (f, g)
+        } else {
+            (g, f)
+        };
+
+        self.nodes[child as usize].children = [a, taller];
+        self.nodes[a as usize].children[side] = shorter;
+        self.nodes[shorter as usize].parent = a;
+
+        let [a0, a1] = self.nodes[a as usize].children;
+        let (n0, n1) = (&self.nodes[a0 as usize], &self.nodes[a1 as usize]);
+        let a_bounds = n0.bounds.union(n1.bounds);
+        let a_height = 1 + n0.height.max(n1.height);
+        self.nodes[a as usize].bounds = a_bounds;
+        self.nodes[a as usize].height = a_height;
+
+        let taller_node = &self.nodes[taller as usize];
+        let child_bounds = a_bounds.union(taller_node.bounds);
+        let child_height = 1 + a_height.max(taller_node.height);
+        self.nodes[child as usize].bounds = child_bounds;
+        self.nodes[child as usize].height = child_height;
+
+        child
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test_utils::Rng;
+
+    impl<T> DynamicAabbTree<T> {
+        fn validate(&self) {
+            if self.root == NULL {
+                assert_eq!(self.leaf_count, 0);
+                return;
+            }
+            assert_eq!(self.nodes[self.root as usize].parent, NULL);
+
+            let mut leaves = 0;
+            let mut stack = vec![self.root];
+            while let Some(index) = stack.pop() {
+                let node = &self.nodes[index as usize];
+                if node.is_leaf() {
+                    assert_eq!(node.height, 0);
+                    assert!(node.data.is_some());
+                    leaves += 1;
+                    continue;
+                }
+
+                let [c0, c1] = node.children;
+                let (n0, n1) = (&self.nodes[c0 as usize], &self.nodes[c1 as usize]);
+                assert_eq!(n0.parent, index);
+                assert_eq!(n1.parent, index);
+                assert_eq!(node.height, 1 + n0.height.max(n1.height));
+                assert!((n0.height - n1.height).abs() <= 1, "Imbalanced tree");
+                assert_eq!(node.bounds, n0.bounds.union(n1.bounds));
+                stack.extend(node.children);
+            }
+            assert_eq!(leaves, self.leaf_count);
+        }
+    }
+
+    fn sorted<T: Ord>(mut v: Vec<T>) -> Vec<T> {
+        v.sort_unstable();
+        v
+    }
+
+    #[test]
+    fn test_balanced_on_sorted_insertion() {
+        let mut tree = DynamicAabbTree::default();
+        for i in 0..1024 {
+            let center = Vec3::new(i as f32, 0.0, 0.0);
+            tree.insert(BoundingBox::from_center_size(center, Vec3::splat(0.5)), i);
+        }
+        tree.validate();
+        assert_eq!(tree.len(), 1024);
+        assert!(tree.height() <= 15, "height: {}", tree.height());
+    }
+
+    #[test]
+    fn test_insert_remove_update() {
+        let mut rng = Rng(0x853c_49e6_748f_ea9b);
+        let mut tree = DynamicAabbTree::new(0.5);
+        let mut boxes = vec![];
+        let mut handles = vec![];
+        for i in 0..300 {
+            let bb = rng.next_box();
+            boxes.push(Some(bb));
+            handles.push(tree.insert(bb, i));
+        }
+        tree.validate();
+
+        for i in (0..300).step_by(3) {
+            assert_eq!(tree.remove(handles[i]), Some(i));
+            assert_eq!(tree.remove(handles[i]), None);
+            boxes[i] = None;
+        }
+        tree.validate();
+        assert_eq!(tree.len(), 200);
+
+        // Small moves stay within the fattened bounds.
+        let i = 1;
+        let bb = boxes[i].unwrap().translated(Vec3::splat(0.1));
+        assert!(!tree.update(handles[i], bb));
+        boxes[i] = Some(bb);
+
+        // Large moves reinsert.
+        for i in (1..300).step_by(3) {
+            let bb = rng.next_box();
+            assert!(tree.update(handles[i], bb));
+            boxes[i] = Some(bb);
+        }
+        tree.validate();
+
+        for (i, bb) in boxes.iter().enumerate() {
+            if let Some(bb) = bb {
+                let fat = tree.fat_bounds(handles[i]).unwrap();
+                assert!(fat.contains(bb.min) && fat.contains(bb.max));
+                assert_eq!(tree.get(handles[i]), Some(&i));
+            }
+        }
+
+        // Queries against brute force on the fat bounds.
+        let fat: Vec<_> = (0..300)
+            .filter(|&i| boxes[i].is_some())
+            .map(|i| (handles[i], tree.fat_bounds(handles[i]).unwrap()))
+            .collect();
+
+        for _ in 0..50 {
+            let query = rng.next_box();
+            let mut found = vec![];
+            tree.for_each_intersecting(&query, |handle, _| found.push(handle));
+            let expected: Vec<_> = fat
+                .iter()
+                .filter(|(_, bb)| bb.intersects(&query))
+                .map(|&(h, _)| h)
+                .collect();
+            assert_eq!(sorted(found), sorted(expected));
+
+            let ray =
+                Ray3::from_origin_dir(100.0 * rng.next_vec3(), (rng.next_vec3() - 0.5).normalize());
+            let hit_t = |bb: &BoundingBox| bb.intersect_ray(&ray).map(|(t, _)| t.max(0.0));
+            let mut found = vec![];
+            tree.for_each_ray_hit(&ray, 50.0, |handle, _| found.push(handle));
+            let expected: Vec<_> = fat
+                .iter()
+                .filter(|(_, bb)| hit_t(bb).is_some_and(|t| t <= 50.0))
+                .map(|&(h, _)| h)
+                .collect();
+            assert_eq!(sorted(found), sorted(expected));
+
+            let nearest = tree.nearest_ray_hit(&ray, 50.0, |handle, _| {
+                hit_t(&tree.fat_bounds(handle).unwrap())
+            });
+            let expected = fat
+                .iter()
+                .filter_map(|&(h, bb)| hit_t(&bb).map(|t| (h, t)))
+                .filter(|&(_, t)| t <= 50.0)
+                .min_by(|a, b| a.1.total_cmp(&b.1));
+            assert_eq!(nearest.map(|n| n.1), expected.map(|e| e.1));
+        }
+
+        let mut pairs = vec![];
+        tree.for_each_pair(|a, b| pairs.push((a, b)));
+        let mut expected = vec![];
+        for (a, a_bb) in &fat {
+            for (b, b_bb) in &fat {
+                if a < b && a_bb.intersects(b_bb) {
+                    expected.push((*a, *b));
+                }
+            }
+        }
+        assert_eq!(sorted(pairs), sorted(expected));
+    }
+}
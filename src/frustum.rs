@@ -0,0 +1,328 @@
+use crate::BoundingBox;
+use crate::BoundingSphere;
+use crate::IsoTransform;
+use crate::Mat4;
+use crate::Plane3;
+use crate::Vec3;
+use crate::Vec4;
+
+/// The result of testing a volume against another volume.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Containment {
+    /// Fully outside.
+    Outside,
+    /// Partially inside (or possibly outside, for conservative tests).
+    Intersecting,
+    /// Fully inside.
+    Inside,
+}
+
+/// A view frustum made up of six planes with their normals pointing inwards.
+///
+/// The planes are in the order: left, right, bottom, top, near, far.
+///
+/// A point is inside the frustum if its distance to every plane is non-negative.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "speedy", derive(speedy::Writable, speedy::Readable))]
+pub struct Frustum {
+    /// Normalized planes, with normals pointing inwards.
+    pub planes: [Plane3; 6],
+}
+
+impl Frustum {
+    /// Extract the frustum from a view-projection matrix with a `[0, 1]` depth range,
+    /// such as the ones created by [`Mat4::perspective_rh`] and [`Mat4::orthographic_rh`].
+    ///
+    /// The resulting planes are in the space the matrix transforms from (usually world space).
+    ///
+    /// Infinite far planes, such as from [`Mat4::perspective_infinite_rh`], are supported.
+    /// Such a far plane will contain every point.
+    pub fn from_view_projection(view_projection: &Mat4) -> Self {
+        let [left, right, bottom, top, near_z, far_z] = clip_planes(view_projection);
+        Self {
+            planes: [left, right, bottom, top, near_z, far_z],
+        }
+    }
+
+    /// Extract the frustum from a view-projection matrix using reverse-Z, i.e. where the
+    /// near plane maps to depth `1` and the far plane maps to depth `0`,
+    /// such as the ones created by [`Mat4::perspective_infinite_reverse_rh`].
+    ///
+    /// The resulting planes are in the space the matrix transforms from (usually world space).
+    ///
+    /// Infinite far planes are supported. Such a far plane will contain every point.
+    pub fn from_view_projection_reverse_z(view_projection: &Mat4) -> Self {
+        let [left, right, bottom, top, near_z, far_z] = clip_planes(view_projection);
+        Self {
+            planes: [left, right, bottom, top, far_z, near_z],
+        }
+    }
+
+    /// The left plane.
+    #[inline]
+    pub fn left(&self) -> Plane3 {
+        self.planes[0]
+    }
+
+    /// The right plane.
+    #[inline]
+    pub fn right(&self) -> Plane3 {
+        self.planes[1]
+    }
+
+    /// The bottom plane.
+    #[inline]
+    pub fn bottom(&self) -> Plane3 {
+        self.planes[2]
+    }
+
+    /// The top plane.
+    #[inline]
+    pub fn top(&self) -> Plane3 {
+        self.planes[3]
+    }
+
+    /// The near plane.
+    #[inline]
+    pub fn near(&self) -> Plane3 {
+        self.planes[4]
+    }
+
+    /// The far plane.
+    #[inline]
+    pub fn far(&self) -> Plane3 {
+        self.planes[5]
+    }
+
+    /// Returns `true` if the point is within (or on the edge of) the frustum.
+    #[must_use]
+    pub fn contains(&self, point: Vec3) -> bool {
+        self.planes.iter().all(|plane| 0.0 <= plane.distance(point))
+    }
+
+    /// Classify a point as either [`Containment::Inside`] or [`Containment::Outside`].
+    ///
+    /// Points on the edge of the frustum are considered inside.
+    pub fn classify_point(&self, point: Vec3) -> Containment {
+        if self.contains(point) {
+            Containment::Inside
+        } else {
+            Containment::Outside
+        }
+    }
+
+    /// Classify a sphere against the frustum.
+    ///
+    /// This is conservative: spheres just outside a corner or edge of the frustum
+    /// may be classified as [`Containment::Intersecting`].
+    pub fn classify_sphere(&self, sphere: &BoundingSphere) -> Containment {
+        if sphere.is_nothing() {
+            return Containment::Outside;
+        }
+
+        let mut result = Containment::Inside;
+        for plane in &self.planes {
+            let dist = plane.distance(sphere.center);
+            if dist < -sphere.radius {
+                return Containment::Outside;
+            } else if dist < sphere.radius {
+                result = Containment::Intersecting;
+            }
+        }
+        result
+    }
+
+    /// Classify a bounding box against the frustum.
+    ///
+    /// This is conservative: boxes just outside a corner or edge of the frustum
+    /// may be classified as [`Containment::Intersecting`].
+    pub fn classify_bounding_box(&self, bb: &BoundingBox) -> Containment {
+        if bb.is_nothing() {
+            return Containment::Outside;
+        }
+
+        classify_bounding_box_by_planes(&self.planes, bb)
+    }
+
+    /// The eight corners of this frustum.
+    ///
+    /// Uses the same order as [`BoundingBox::corners`], with the minimum and maximum
+    /// along X, Y and Z replaced by left/right, bottom/top and near/far respectively.
+    ///
+    /// The far corners are non-finite if the far plane is infinitely far away.
+    pub fn corners(&self) -> [Vec3; 8] {
+        let [left, right, bottom, top, near, far] = self.planes;
+        let mut corners = [Vec3::ZERO; 8];
+        for (i, corner) in corners.iter_mut().enumerate() {
+            *corner = Plane3::intersect_three_planes(
+                if i & 4 == 0 { &left } else { &right },
+                if i & 2 == 0 { &bottom } else { &top },
+                if i & 1 == 0 { &near } else { &far },
+            )
+            .unwrap_or(Vec3::NAN);
+        }
+        corners
+    }
+
+    /// Return this frustum after it has been transformed.
+    #[must_use]
+    pub fn transform_iso(&self, m: &IsoTransform) -> Self {
+        Self {
+            // Infinitely far planes (zero normal, infinite distance) stay that way.
+            planes: self.planes.map(|plane| plane.transform_iso(m)),
+        }
+    }
+}
+
+/// Classify a bounding box against the intersection of planes with inward normals.
+///
+/// This is conservative: boxes just outside a corner or edge of the volume
+/// may be classified as [`Containment::Intersecting`].
+pub(crate) fn classify_bounding_box_by_planes(planes: &[Plane3], bb: &BoundingBox) -> Containment {
+    let mut result = Containment::Inside;
+    for plane in planes {
+        // The corners furthest along and against the plane normal.
+        // Axes the plane is parallel to are ignored, which handles infinite boxes.
+        let n = plane.normal;
+        let zero = Vec3::ZERO;
+        let p_vertex = Vec3::select(
+            n.cmpgt(zero),
+            bb.max,
+            Vec3::select(n.cmplt(zero), bb.min, zero),
+        );
+        let n_vertex = Vec3::select(
+            n.cmpgt(zero),
+            bb.min,
+            Vec3::select(n.cmplt(zero), bb.max, zero),
+        );
+
+        if plane.distance(p_vertex) < 0.0 {
+            return Containment::Outside;
+        } else if plane.distance(n_vertex) < 0.0 {
+            result = Containment::Intersecting;
+        }
+    }
+    result
+}
+
+/// Clip space planes in the order `-w <= x`, `x <= w`, `-w <= y`, `y <= w`, `0 <= z`, `z <= w`.
+fn clip_planes(m: &Mat4) -> [Plane3; 6] {
+    // Gribb & Hartmann: "Fast Extraction of Viewing Frustum Planes from the World-View-Projection Matrix"
+    let [r0, r1, r2, r3] = [m.row(0), m.row(1), m.row(2), m.row(3)];
+    [r3 + r0, r3 - r0, r3 + r1, r3 - r1, r2, r3 - r2].map(plane_from_vec4)
+}
+
+fn plane_from_vec4(v: Vec4) -> Plane3 {
+    let plane = Plane3::from_normal_dist(v.truncate(), v.w);
+    if plane.normal.length_squared() <= f32::EPSILON * f32::EPSILON * plane.d * plane.d {
+        // An infinitely far away plane: contains every point.
+        Plane3::from_normal_dist(Vec3::ZERO, f32::INFINITY)
+    } else {
+        plane.normalized()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::f32::consts::FRAC_PI_2;
+
+    fn view() -> IsoTransform {
+        IsoTransform::look_at_rh(Vec3::new(0.0, 0.0, 5.0), Vec3::ZERO, Vec3::Y).unwrap()
+    }
+
+    #[test]
+    fn test_from_view_projection() {
+        // 90 degree fov, looking down -Z from z=5. Near plane at z=4, far plane at z=-5.
+        let projection = Mat4::perspective_rh(FRAC_PI_2, 1.0, 1.0, 10.0);
+        let reverse_projection = Mat4::perspective_rh(FRAC_PI_2, 1.0, 10.0, 1.0);
+
+        for frustum in [
+            Frustum::from_view_projection(&(projection * view())),
+            Frustum::from_view_projection_reverse_z(&(reverse_projection * view())),
+        ] {
+            assert!(frustum.contains(Vec3::ZERO));
+            assert!(frustum.contains(Vec3::new(0.0, 0.0, 3.9)));
+            assert!(!frustum.contains(Vec3::new(0.0, 0.0, 4.1)));
+            assert!(frustum.contains(Vec3::new(0.0, 0.0, -4.9)));
+            assert!(!frustum.contains(Vec3::new(0.0, 0.0, -5.1)));
+            assert!(frustum.contains(Vec3::new(4.9, 0.0, 0.0)));
+            assert!(!frustum.contains(Vec3::new(5.1, 0.0, 0.0)));
+            assert!(!frustum.contains(Vec3::new(0.0, -5.1, 0.0)));
+
+            let corners = frustum.corners();
+            assert!(corners[0].abs_diff_eq(Vec3::new(-1.0, -1.0, 4.0), 1e-4));
+            assert!(corners[7].abs_diff_eq(Vec3::new(10.0, 10.0, -5.0), 1e-3));
+            for corner in corners {
+                assert!(frustum.planes.iter().all(|p| p.distance(corner) > -1e-3));
+            }
+        }
+    }
+
+    #[test]
+    fn test_infinite_far_plane() {
+        let projection = Mat4::perspective_infinite_reverse_rh(FRAC_PI_2, 1.0, 1.0);
+        let frustum = Frustum::from_view_projection_reverse_z(&(projection * view()));
+        assert!(frustum.contains(Vec3::new(0.0, 0.0, -1e6)));
+        assert!(!frustum.contains(Vec3::new(0.0, 0.0, 4.1)));
+        assert!(!frustum.corners()[7].is_finite());
+
+        let projection = Mat4::perspective_infinite_rh(FRAC_PI_2, 1.0, 1.0);
+        let frustum = Frustum::from_view_projection(&(projection * view()));
+        assert!(frustum.contains(Vec3::new(0.0, 0.0, -1e6)));
+        assert_eq!(
+            frustum.classify_bounding_box(&BoundingBox::everything()),
+            Containment::Intersecting
+        );
+    }
+
+    #[test]
+    fn test_classify() {
+        let projection = Mat4::perspective_rh(FRAC_PI_2, 1.0, 1.0, 10.0);
+        let frustum = Frustum::from_view_projection(&(projection * view()));
+
+        let inside = BoundingBox::from_center_size(Vec3::ZERO, Vec3::ONE);
+        let crossing = BoundingBox::from_center_size(Vec3::new(0.0, 0.0, 4.0), Vec3::ONE);
+        let outside = BoundingBox::from_center_size(Vec3::new(0.0, 0.0, 6.0), Vec3::ONE);
+        assert_eq!(frustum.classify_bounding_box(&inside), Containment::Inside);
+        assert_eq!(
+            frustum.classify_bounding_box(&crossing),
+            Containment::Intersecting
+        );
+        assert_eq!(
+            frustum.classify_bounding_box(&outside),
+            Containment::Outside
+        );
+        assert_eq!(
+            frustum.classify_bounding_box(&BoundingBox::nothing()),
+            Containment::Outside
+        );
+
+        for (bb, expected) in [
+            (inside, Containment::Inside),
+            (crossing, Containment::Intersecting),
+            (outside, Containment::Outside),
+        ] {
+            let sphere = BoundingSphere::from_center_radius(bb.center(), 0.5);
+            assert_eq!(frustum.classify_sphere(&sphere), expected);
+            assert_eq!(
+                frustum.classify_point(bb.center()) == Containment::Inside,
+                expected != Containment::Outside
+            );
+        }
+    }
+
+    #[test]
+    fn test_transform_iso() {
+        let projection = Mat4::perspective_rh(FRAC_PI_2, 1.0, 1.0, 10.0);
+        let local = Frustum::from_view_projection(&projection);
+        let world = Frustum::from_view_projection(&(projection * view()));
+
+        let transformed = local.transform_iso(&view().inverse());
+        for (a, b) in transformed.planes.iter().zip(&world.planes) {
+            assert!(a.normal.abs_diff_eq(b.normal, 1e-5));
+            assert!((a.d - b.d).abs() < 1e-4);
+        }
+    }
+}
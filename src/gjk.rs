@@ -0,0 +1,650 @@
+use crate::Quat;
+use crate::Support;
+use crate::Vec3;
+use crate::triangle::closest_barycentric_on_triangle;
+use std::collections::HashSet;
+
+/// The closest points of two separated shapes, see [`gjk_distance`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ClosestPoints {
+    /// The distance between `point_a` and `point_b`.
+    pub distance: f32,
+    /// The point on the first shape closest to the second shape.
+    pub point_a: Vec3,
+    /// The point on the second shape closest to the first shape.
+    pub point_b: Vec3,
+}
+
+/// How far two shapes overlap, see [`epa_penetration`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Penetration {
+    /// How far the second shape needs to move along `normal` to only touch the first shape.
+    pub depth: f32,
+    /// The normalized contact normal, pointing from the first shape into the second.
+    pub normal: Vec3,
+    /// The point of the first shape furthest into the second shape.
+    pub point_a: Vec3,
+    /// The point of the second shape furthest into the first shape.
+    pub point_b: Vec3,
+}
+
+/// Find the closest points of two convex shapes using the GJK algorithm.
+///
+/// Returns [`None`] if the shapes overlap or touch.
+/// Curved shapes such as spheres are approximated to a relative precision of about `1e-5`.
+///
+/// Use [`crate::Transformed`] to place shapes in the world.
+pub fn gjk_distance<A, B>(a: &A, b: &B) -> Option<ClosestPoints>
+where
+    A: Support + ?Sized,
+    B: Support + ?Sized,
+{
+    match gjk(a, b) {
+        Gjk::Separated(closest) => Some(closest),
+        Gjk::Overlapping(_) => None,
+    }
+}
+
+/// Returns `true` if the convex shapes overlap or touch, using the GJK algorithm.
+#[inline]
+pub fn gjk_intersects<A, B>(a: &A, b: &B) -> bool
+where
+    A: Support + ?Sized,
+    B: Support + ?Sized,
+{
+    gjk_distance(a, b).is_none()
+}
+
+/// Find how far two convex shapes overlap using GJK followed by the
+/// expanding polytope algorithm (EPA).
+///
+/// Moving the second shape by `normal * depth` separates them.
+/// Returns [`None`] if the shapes do not overlap.
+/// Curved shapes such as spheres are approximated, so expect a relative error around `1e-3`.
+///
+/// Use [`crate::Transformed`] to place shapes in the world.
+pub fn epa_penetration<A, B>(a: &A, b: &B) -> Option<Penetration>
+where
+    A: Support + ?Sized,
+    B: Support + ?Sized,
+{
+    match gjk(a, b) {
+        Gjk::Separated(_) => None,
+        Gjk::Overlapping(simplex) => Some(epa(a, b, simplex)),
+    }
+}
+
+const GJK_MAX_ITERATIONS: usize = 64;
+const EPA_MAX_ITERATIONS: usize = 128;
+
+/// Relative precision at which to stop refining the result.
+const RELATIVE_TOLERANCE: f32 = 1e-5;
+
+/// A point on the Minkowski difference `a - b`, and the points of `a` and `b` it came from.
+#[derive(Clone, Copy, Debug, Default)]
+struct SupportPoint {
+    w: Vec3,
+    a: Vec3,
+    b: Vec3,
+}
+
+impl SupportPoint {
+    fn new<A, B>(a: &A, b: &B, dir: Vec3) -> Self
+    where
+        A: Support + ?Sized,
+        B: Support + ?Sized,
+    {
+        let a = a.support(dir);
+        let b = b.support(-dir);
+        Self { w: a - b, a, b }
+    }
+}
+
+/// Up to four support points, with the barycentric weights of the point closest to the origin.
+#[derive(Clone, Copy, Debug, Default)]
+struct Simplex {
+    points: [SupportPoint; 4],
+    weights: [f32; 4],
+    len: usize,
+}
+
+enum Gjk {
+    Separated(ClosestPoints),
+    Overlapping(Simplex),
+}
+
+fn gjk<A, B>(a: &A, b: &B) -> Gjk
+where
+    A: Support + ?Sized,
+    B: Support + ?Sized,
+{
+    let first = SupportPoint::new(a, b, Vec3::X);
+    let mut simplex = Simplex {
+        points: [first; 4],
+        weights: [1.0, 0.0, 0.0, 0.0],
+        len: 1,
+    };
+    let mut best = simplex;
+    let mut v = first.w;
+    let mut scale_squared = v.length_squared();
+
+    for _ in 0..GJK_MAX_ITERATIONS {
+        let v_length_squared = v.length_squared();
+        if v_length_squared <= RELATIVE_TOLERANCE * RELATIVE_TOLERANCE * scale_squared {
+            return Gjk::Overlapping(simplex);
+        }
+
+        // Stop when the shapes are no further apart along `v` than the current closest points.
+        let p = SupportPoint::new(a, b, -v);
+        scale_squared = scale_squared.max(p.w.length_squared());
+        if v_length_squared - v.dot(p.w) <= RELATIVE_TOLERANCE * v_length_squared
+            || simplex.points[..simplex.len].iter().any(|q| q.w == p.w)
+        {
+            break;
+        }
+
+        simplex.points[simplex.len] = p;
+        simplex.len += 1;
+        if !simplex.reduce_to_closest() {
+            return Gjk::Overlapping(simplex);
+        }
+
+        let closest = simplex.closest();
+        if v_length_squared <= closest.length_squared() {
+            // No progress, due to rounding errors.
+            break;
+        }
+        v = closest;
+        best = simplex;
+    }
+
+    let (point_a, point_b) = best.closest_points();
+    Gjk::Separated(ClosestPoints {
+        distance: point_a.distance(point_b),
+        point_a,
+        point_b,
+    })
+}
+
+impl Simplex {
+    fn closest(&self) -> Vec3 {
+        (0..self.len)
+            .map(|i| self.weights[i] * self.points[i].w)
+            .sum()
+    }
+
+    fn closest_points(&self) -> (Vec3, Vec3) {
+        let point_a = (0..self.len)
+            .map(|i| self.weights[i] * self.points[i].a)
+            .sum();
+        let point_b = (0..self.len)
+            .map(|i| self.weights[i] * self.points[i].b)
+            .sum();
+        (point_a, point_b)
+    }
+
+    /// Find the point of the simplex closest to the origin, and remove the points not needed
+    /// to express it.
+    ///
+    /// Returns `false` if the simplex is a tetrahedron containing the origin.
+    fn reduce_to_closest(&mut self) -> bool {
+        let w = self.points.map(|p| p.w);
+        match self.len {
+            1 => self.weights[0] = 1.0,
+            2 => {
+                let ab = w[1] - w[0];
+                let t = if ab == Vec3::ZERO {
+                    0.0
+                } else {
+                    (-w[0].dot(ab) / ab.length_squared()).clamp(0.0, 1.0)
+                };
+                self.weights[..2].copy_from_slice(&[1.0 - t, t]);
+            }
+            3 => {
+                let weights = closest_barycentric_on_triangle(Vec3::ZERO, [w[0], w[1], w[2]]);
+                if weights.iter().all(|x| x.is_finite()) {
+                    self.weights[..3].copy_from_slice(&weights);
+                } else {
+                    // A degenerate triangle, so use the closest of its edges.
+                    self.keep_closest_face(&[[0, 1], [1, 2], [2, 0]]);
+                }
+            }
+            _ => {
+                // The sides of a (nearly) flat tetrahedron are too imprecise to tell
+                // if the origin is inside, so then we consider every face.
+                let size = (1..4).map(|i| w[i].distance(w[0])).fold(0.0, f32::max);
+                let volume = (w[1] - w[0]).dot((w[2] - w[0]).cross(w[3] - w[0]));
+                let is_flat = volume.abs() <= RELATIVE_TOLERANCE * size * size * size;
+
+                // The faces where the origin is outside, i.e. on the other side from the fourth point.
+                let faces = [[1, 2, 3], [0, 2, 3], [0, 1, 3], [0, 1, 2]];
+                let outside: Vec<[usize; 3]> = (0..4)
+                    .filter(|&opposite| {
+                        let [i, j, k] = faces[opposite];
+                        let normal = (w[j] - w[i]).cross(w[k] - w[i]);
+                        is_flat || normal.dot(w[opposite] - w[i]) * normal.dot(-w[i]) <= 0.0
+                    })
+                    .map(|opposite| faces[opposite])
+                    .collect();
+                if outside.is_empty() {
+                    return false;
+                }
+                self.keep_closest_face(&outside);
+            }
+        }
+        self.remove_unused();
+        true
+    }
+
+    /// Keep only the points of the face closest to the origin, and find the closest point on it.
+    fn keep_closest_face<const N: usize>(&mut self, faces: &[[usize; N]]) {
+        let closest = faces
+            .iter()
+            .map(|face| {
+                let mut sub = Simplex {
+                    points: self.points,
+                    weights: [0.0; 4],
+                    len: N,
+                };
+                for (i, &vertex) in face.iter().enumerate() {
+                    sub.points[i] = self.points[vertex];
+                }
+                sub.reduce_to_closest();
+                sub
+            })
+            .min_by(|s0, s1| {
+                s0.closest()
+                    .length_squared()
+                    .total_cmp(&s1.closest().length_squared())
+            });
+        if let Some(closest) = closest {
+            *self = closest;
+        }
+    }
+
+    fn remove_unused(&mut self) {
+        let mut len = 0;
+        for i in 0..self.len {
+            if self.weights[i] > 0.0 {
+                self.points[len] = self.points[i];
+                self.weights[len] = self.weights[i];
+                len += 1;
+            }
+        }
+        if len == 0 {
+            // Can only happen through rounding errors. Keep the first point.
+            self.weights[0] = 1.0;
+            len = 1;
+        }
+        self.len = len;
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+struct EpaFace {
+    vertices: [usize; 3],
+    /// Normalized, pointing outwards.
+    normal: Vec3,
+    /// Distance from the origin to the plane of the face.
+    dist: f32,
+}
+
+impl EpaFace {
+    fn new(points: &[SupportPoint], vertices: [usize; 3]) -> Option<Self> {
+        let [a, b, c] = vertices.map(|i| points[i].w);
+        let normal = (b - a).cross(c - a).try_normalize()?;
+        Some(Self {
+            vertices,
+            normal,
+            dist: normal.dot(a),
+        })
+    }
+}
+
+fn epa<A, B>(a: &A, b: &B, simplex: Simplex) -> Penetration
+where
+    A: Support + ?Sized,
+    B: Support + ?Sized,
+{
+    let mut points = simplex.points[..simplex.len].to_vec();
+    let scale = points
+        .iter()
+        .map(|p| p.w.length())
+        .fold(f32::EPSILON, f32::max);
+    let tolerance = RELATIVE_TOLERANCE * scale;
+
+    // Grow the simplex into a tetrahedron. The origin is on the simplex, so it will be in it.
+    if points.len() == 1 {
+        let dirs = [
+            Vec3::X,
+            Vec3::NEG_X,
+            Vec3::Y,
+            Vec3::NEG_Y,
+            Vec3::Z,
+            Vec3::NEG_Z,
+        ];
+        points.extend(
+            dirs.iter()
+                .map(|&dir| SupportPoint::new(a, b, dir))
+                .find(|p| p.w.distance(points[0].w) > tolerance),
+        );
+    }
+    if points.len() == 2 {
+        let line = (points[1].w - points[0].w).normalize();
+        let perpendicular = line.any_orthonormal_vector();
+        points.extend(
+            (0..6)
+                .map(|i| Quat::from_axis_angle(line, i as f32 * core::f32::consts::FRAC_PI_3))
+                .map(|rotation| SupportPoint::new(a, b, rotation * perpendicular))
+                .find(|p| (p.w - points[0].w).reject_from_normalized(line).length() > tolerance),
+        );
+    }
+    if points.len() == 3 {
+        let normal = (points[1].w - points[0].w)
+            .cross(points[2].w - points[0].w)
+            .normalize_or_zero();
+        let height = |p: &SupportPoint| normal.dot(p.w - points[0].w).abs();
+        let above = SupportPoint::new(a, b, normal);
+        let below = SupportPoint::new(a, b, -normal);
+        let furthest = if height(&above) >= height(&below) {
+            above
+        } else {
+            below
+        };
+        if height(&furthest) > tolerance {
+            points.push(furthest);
+        }
+    }
+    if points.len() < 4 {
+        // The shapes are flat and lie in the same plane (or line), so they only touch.
+        let (point_a, point_b) = simplex.closest_points();
+        let normal = if points.len() == 3 {
+            (points[1].w - points[0].w)
+                .cross(points[2].w - points[0].w)
+                .normalize_or(Vec3::X)
+        } else {
+            Vec3::X
+        };
+        return Penetration {
+            depth: 0.0,
+            normal,
+            point_a,
+            point_b,
+        };
+    }
+
+    // Make the first face point away from the fourth point, so that all faces point outwards.
+    let [w0, w1, w2, w3] = [0, 1, 2, 3].map(|i| points[i].w);
+    if (w1 - w0).cross(w2 - w0).dot(w3 - w0) > 0.0 {
+        points.swap(1, 2);
+    }
+    let mut faces: Vec<EpaFace> = [[0, 1, 2], [0, 3, 1], [1, 3, 2], [2, 3, 0]]
+        .iter()
+        .filter_map(|&vertices| EpaFace::new(&points, vertices))
+        .collect();
+
+    let mut closest = closest_face(&faces);
+    for _ in 0..EPA_MAX_ITERATIONS {
+        let p = SupportPoint::new(a, b, closest.normal);
+        if closest.normal.dot(p.w) - closest.dist <= tolerance {
+            break;
+        }
+
+        // Remove the faces that can see the new point, and connect their outline to it.
+        let new_index = points.len();
+        points.push(p);
+        let mut visible_edges = HashSet::new();
+        let mut kept = Vec::with_capacity(faces.len());
+        for face in &faces {
+            let [i, j, k] = face.vertices;
+            if face.normal.dot(p.w - points[i].w) > 0.0 {
+                visible_edges.extend([(i, j), (j, k), (k, i)]);
+            } else {
+                kept.push(*face);
+            }
+        }
+        let new_faces: Option<Vec<EpaFace>> = visible_edges
+            .iter()
+            .filter(|&&(i, j)| !visible_edges.contains(&(j, i)))
+            .map(|&(i, j)| EpaFace::new(&points, [i, j, new_index]))
+            .collect();
+        let Some(new_faces) = new_faces else {
+            // A degenerate face, so we are as precise as we can get.
+            break;
+        };
+        kept.extend(new_faces);
+        faces = kept;
+        closest = closest_face(&faces);
+    }
+
+    // The contact is where the origin projects onto the closest face.
+    let triangle = closest.vertices.map(|i| points[i]);
+    let weights =
+        closest_barycentric_on_triangle(closest.normal * closest.dist, triangle.map(|p| p.w));
+    let point_a = (0..3).map(|i| weights[i] * triangle[i].a).sum();
+    let point_b = (0..3).map(|i| weights[i] * triangle[i].b).sum();
+    Penetration {
+        depth: closest.dist.max(0.0),
+        normal: closest.normal,
+        point_a,
+        point_b,
+    }
+}
+
+fn closest_face(faces: &[EpaFace]) -> EpaFace {
+    *faces
+        .iter()
+        .min_by(|f0, f1| f0.dist.total_cmp(&f1.dist))
+        .expect("The polytope always has faces")
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::BoundingBox;
+    use crate::BoundingSphere;
+    use crate::Capsule3;
+    use crate::Conformal3;
+    use crate::IsoTransform;
+    use crate::Obb3;
+    use crate::Segment3;
+    use crate::Transformed;
+    use crate::Vec3Swizzles;
+
+    #[test]
+    fn test_gjk_distance() {
+        let sphere_a = BoundingSphere::from_center_radius(Vec3::ZERO, 1.0);
+        let sphere_b = BoundingSphere::from_center_radius(Vec3::new(5.0, 0.0, 0.0), 2.0);
+        let closest = gjk_distance(&sphere_a, &sphere_b).unwrap();
+        assert!((closest.distance - 2.0).abs() < 1e-3);
+        assert!(closest.point_a.abs_diff_eq(Vec3::X, 1e-2));
+        assert!(closest.point_b.abs_diff_eq(Vec3::new(3.0, 0.0, 0.0), 1e-2));
+
+        let bb_a = BoundingBox::from_min_max(Vec3::ZERO, Vec3::ONE);
+        let bb_b = BoundingBox::from_min_max(Vec3::new(2.0, 3.0, 0.5), Vec3::splat(4.0));
+        let closest = gjk_distance(&bb_a, &bb_b).unwrap();
+        assert!((closest.distance - 5.0_f32.sqrt()).abs() < 1e-5);
+        assert!(
+            closest
+                .point_a
+                .abs_diff_eq(Vec3::new(1.0, 1.0, closest.point_a.z), 1e-5)
+        );
+        assert!((0.5..=1.0).contains(&closest.point_a.z));
+
+        let capsule = Capsule3::new(Vec3::ZERO, Vec3::new(0.0, 4.0, 0.0), 0.5);
+        let points = [
+            Vec3::new(2.0, 2.0, 0.0),
+            Vec3::new(3.0, 1.0, 1.0),
+            Vec3::new(2.0, 5.0, -1.0),
+        ];
+        let closest = gjk_distance(&capsule, points.as_slice()).unwrap();
+        assert!((closest.distance - 1.5).abs() < 1e-4);
+
+        assert!(gjk_intersects(
+            &bb_a,
+            &BoundingSphere::from_center_radius(Vec3::splat(1.5), 1.0)
+        ));
+        assert!(!gjk_intersects(
+            &bb_a,
+            &BoundingSphere::from_center_radius(Vec3::splat(2.0), 1.0)
+        ));
+        assert_eq!(gjk_distance(&bb_a, &Vec3::splat(0.5)), None);
+    }
+
+    #[test]
+    fn test_gjk_matches_exact() {
+        // Compare with the exact segment and triangle distances, for many placements.
+        let triangle = [
+            Vec3::ZERO,
+            Vec3::new(2.0, 0.0, 0.0),
+            Vec3::new(0.5, 2.0, 0.5),
+        ];
+        for i in 0..100 {
+            let f = i as f32;
+            let a = Vec3::new((f * 0.7).sin(), (f * 1.3).cos(), (f * 0.3).sin()) * 3.0;
+            let b = a + Vec3::new((f * 2.1).cos(), (f * 0.9).sin(), (f * 1.7).cos());
+            let segment = Segment3::new(a, b);
+            let other = Segment3::new(-b, a.yzx());
+
+            let exact = segment.distance_to_triangle(triangle);
+            match gjk_distance(&segment, &triangle) {
+                Some(closest) => assert!((closest.distance - exact).abs() < 1e-4),
+                None => assert!(exact < 1e-4),
+            }
+
+            let exact = segment.distance_to_segment(&other);
+            match gjk_distance(&segment, &other) {
+                Some(closest) => {
+                    assert!((closest.distance - exact).abs() < 1e-4);
+                    assert!(segment.distance_to_point(closest.point_a) < 1e-4);
+                    assert!(other.distance_to_point(closest.point_b) < 1e-4);
+                }
+                None => assert!(exact < 1e-4),
+            }
+        }
+    }
+
+    #[test]
+    fn test_transformed() {
+        let bb = BoundingBox::from_min_max(Vec3::splat(-1.0), Vec3::ONE);
+        let iso = IsoTransform::from_rotation_translation(
+            Quat::from_rotation_z(core::f32::consts::FRAC_PI_4),
+            Vec3::new(3.0, 0.0, 0.0),
+        );
+        let placed = Transformed::new(&bb, iso);
+
+        // The rotated box reaches sqrt(2) towards the origin.
+        let closest = gjk_distance(&placed, &Vec3::ZERO).unwrap();
+        assert!((closest.distance - (3.0 - 2.0_f32.sqrt())).abs() < 1e-5);
+        let obb = Obb3::from_bounding_box_iso(&bb, &iso);
+        let closest_obb = gjk_distance(&obb, &Vec3::ZERO).unwrap();
+        assert!((closest.distance - closest_obb.distance).abs() < 1e-5);
+
+        let conformal = Conformal3::from_scale_rotation_translation(
+            2.0,
+            Quat::IDENTITY,
+            Vec3::new(0.0, 5.0, 0.0),
+        );
+        let placed = Transformed::new(&bb, conformal);
+        let closest = gjk_distance(&placed, &Vec3::ZERO).unwrap();
+        assert!((closest.distance - 3.0).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_epa_penetration() {
+        let bb_a = BoundingBox::from_min_max(Vec3::ZERO, Vec3::ONE);
+        let bb_b = BoundingBox::from_min_max(Vec3::new(0.75, 0.1, 0.2), Vec3::new(2.0, 0.9, 3.0));
+        let penetration = epa_penetration(&bb_a, &bb_b).unwrap();
+        assert!((penetration.depth - 0.25).abs() < 1e-5);
+        assert!(penetration.normal.abs_diff_eq(Vec3::X, 1e-5));
+        assert!((penetration.point_a.x - 1.0).abs() < 1e-5);
+        assert!((penetration.point_b.x - 0.75).abs() < 1e-5);
+
+        let sphere_a = BoundingSphere::from_center_radius(Vec3::ZERO, 1.0);
+        let sphere_b = BoundingSphere::from_center_radius(Vec3::new(0.0, -1.5, 0.0), 1.0);
+        let penetration = epa_penetration(&sphere_a, &sphere_b).unwrap();
+        assert!((penetration.depth - 0.5).abs() < 1e-2);
+        assert!(penetration.normal.abs_diff_eq(Vec3::NEG_Y, 1e-2));
+        assert!(penetration.point_a.abs_diff_eq(Vec3::NEG_Y, 1e-2));
+        assert!(
+            penetration
+                .point_b
+                .abs_diff_eq(Vec3::new(0.0, -0.5, 0.0), 1e-2)
+        );
+
+        // Moving the second shape out by the penetration separates them.
+        let capsule = Capsule3::new(Vec3::new(-1.0, 0.3, 0.5), Vec3::new(1.0, 0.5, 0.5), 0.4);
+        let penetration = epa_penetration(&bb_a, &capsule).unwrap();
+        let offset = penetration.normal * (penetration.depth + 1e-3);
+        let moved = Capsule3::new(capsule.a + offset, capsule.b + offset, capsule.radius);
+        assert!(!gjk_intersects(&bb_a, &moved));
+        assert!(gjk_intersects(
+            &bb_a,
+            &Capsule3::new(
+                capsule.a + 0.9 * offset,
+                capsule.b + 0.9 * offset,
+                capsule.radius
+            )
+        ));
+
+        // Touching at a single point, and deep overlap of identical shapes.
+        let corner = BoundingBox::from_min_max(Vec3::ONE, Vec3::splat(2.0));
+        assert!(epa_penetration(&bb_a, &corner).unwrap().depth < 1e-5);
+        let same = epa_penetration(&bb_a, &bb_a).unwrap();
+        assert!((same.depth - 1.0).abs() < 1e-5);
+
+        let far = BoundingSphere::from_center_radius(Vec3::splat(5.0), 1.0);
+        assert_eq!(epa_penetration(&bb_a, &far), None);
+    }
+
+    #[test]
+    fn test_epa_separates() {
+        // Many overlapping placements: moving by the penetration must just separate the shapes.
+        let points = [Vec3::ZERO, Vec3::X, Vec3::Y, Vec3::Z * 0.5];
+        for i in 0..500 {
+            let f = i as f32;
+            let offset = Vec3::new((f * 0.7).sin(), (f * 1.3).cos(), (f * 0.3).sin()) * 1.5;
+            let obb_a = Obb3::from_center_half_size_rotation(
+                Vec3::ZERO,
+                Vec3::new(1.0, 0.5, 0.7),
+                Quat::from_rotation_y(f * 0.37),
+            );
+            let obb_b = Obb3::from_center_half_size_rotation(
+                offset,
+                Vec3::new(0.3, 0.8, 0.4),
+                Quat::from_rotation_x(f * 0.53) * Quat::from_rotation_z(f * 0.11),
+            );
+            if let Some(penetration) = epa_penetration(&obb_a, &obb_b) {
+                let moved = |t: f32| Obb3 {
+                    center: obb_b.center + t * penetration.normal,
+                    ..obb_b
+                };
+                assert!(!gjk_intersects(&obb_a, &moved(penetration.depth + 1e-3)));
+                if penetration.depth > 1e-2 {
+                    assert!(gjk_intersects(&obb_a, &moved(0.9 * penetration.depth)));
+                }
+            }
+
+            // Curved shapes are only approximated.
+            let capsule = Capsule3::new(Vec3::NEG_X, Vec3::new(1.0, (f * 0.1).sin(), 0.0), 0.5);
+            let sphere = BoundingSphere::from_center_radius(offset, 0.6);
+            let exact = capsule.segment().distance_to_point(offset) - 1.1;
+            match epa_penetration(&capsule, &sphere) {
+                Some(penetration) => assert!((penetration.depth + exact).abs() < 1e-2),
+                None => assert!(
+                    (gjk_distance(&capsule, &sphere).unwrap().distance - exact).abs() < 1e-3
+                ),
+            }
+
+            let placed = |translation| {
+                let iso =
+                    IsoTransform::from_rotation_translation(Quat::from_rotation_x(f), translation);
+                Transformed::new(points.as_slice(), iso)
+            };
+            if let Some(penetration) = epa_penetration(&capsule, &placed(offset)) {
+                let moved = placed(offset + (penetration.depth + 1e-2) * penetration.normal);
+                assert!(!gjk_intersects(&capsule, &moved));
+            }
+        }
+    }
+}
@@ -0,0 +1,381 @@
+use crate::BoundingBox;
+use crate::IVec3;
+use crate::Vec3;
+
+/// A 3-dimensional axis-aligned box of integer cells, such as voxels or tiles.
+///
+/// Unlike [`BoundingBox`], the maximum is exclusive: the box covers the cells
+/// `min.x..max.x`, `min.y..max.y` and `min.z..max.z`.
+/// Cell `c` covers the space from `c * cell_size` to `(c + 1) * cell_size`.
+#[derive(Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "speedy", derive(speedy::Writable, speedy::Readable))]
+#[cfg_attr(feature = "speedy", allow(clippy::manual_slice_size_calculation))]
+pub struct IBoundingBox {
+    /// Minimum cell (inclusive).
+    pub min: IVec3,
+    /// Maximum cell (exclusive).
+    pub max: IVec3,
+}
+
+impl core::fmt::Debug for IBoundingBox {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{:?}..{:?}", self.min, self.max)
+    }
+}
+
+impl IBoundingBox {
+    /// An [`IBoundingBox`] that contains no cells.
+    ///
+    /// This is useful as the seed for bounding boxes.
+    #[inline]
+    pub fn nothing() -> Self {
+        Self {
+            min: IVec3::MAX,
+            max: IVec3::MIN,
+        }
+    }
+
+    /// Create a box from an inclusive minimum and an exclusive maximum cell.
+    #[inline]
+    pub fn from_min_max_exclusive(min: IVec3, max: IVec3) -> Self {
+        Self { min, max }
+    }
+
+    /// Create a box from an inclusive minimum and an inclusive maximum cell.
+    ///
+    /// The exclusive maximum can't go past [`i32::MAX`], so neither can the box.
+    #[inline]
+    pub fn from_min_max_inclusive(min: IVec3, max: IVec3) -> Self {
+        Self {
+            min,
+            max: max.saturating_add(IVec3::ONE),
+        }
+    }
+
+    /// Create a box from a minimum cell and a size in cells.
+    ///
+    /// The box is clamped to end at [`i32::MAX`].
+    #[inline]
+    pub fn from_min_size(min: IVec3, size: IVec3) -> Self {
+        Self {
+            min,
+            max: min.saturating_add(size),
+        }
+    }
+
+    /// Create a box containing a single cell.
+    #[inline]
+    pub fn from_cell(cell: IVec3) -> Self {
+        Self::from_min_max_inclusive(cell, cell)
+    }
+
+    /// Create a box from an iterator of cells that the box will cover.
+    pub fn from_cells(cells: impl Iterator<Item = IVec3>) -> Self {
+        let mut bb = Self::nothing();
+        for cell in cells {
+            bb.extend(cell);
+        }
+        bb
+    }
+
+    /// The smallest box containing every cell that `bb` touches,
+    /// where each cell has the size `cell_size`.
+    ///
+    /// A box lying exactly on a cell boundary only covers the cells on its inside,
+    /// except for flat boxes which always cover at least one cell along each axis.
+    pub fn from_bounding_box_outward(bb: &BoundingBox, cell_size: Vec3) -> Self {
+        if bb.is_nothing() {
+            return Self::nothing();
+        }
+        let min = (bb.min / cell_size).floor().as_ivec3();
+        let max = (bb.max / cell_size).ceil().as_ivec3();
+        Self {
+            min,
+            max: max.max(min.saturating_add(IVec3::ONE)),
+        }
+    }
+
+    /// The largest box containing only cells that are fully inside `bb`,
+    /// where each cell has the size `cell_size`.
+    ///
+    /// Returns [`Self::nothing`] if no cell fits inside `bb`.
+    pub fn from_bounding_box_inward(bb: &BoundingBox, cell_size: Vec3) -> Self {
+        let ibb = Self {
+            min: (bb.min / cell_size).ceil().as_ivec3(),
+            max: (bb.max / cell_size).floor().as_ivec3(),
+        };
+        if ibb.is_nothing() {
+            Self::nothing()
+        } else {
+            ibb
+        }
+    }
+
+    /// The space covered by the cells of this box, where each cell has the size `cell_size`.
+    ///
+    /// Returns [`BoundingBox::nothing`] if this box contains no cells.
+    pub fn to_bounding_box(&self, cell_size: Vec3) -> BoundingBox {
+        if self.is_nothing() {
+            BoundingBox::nothing()
+        } else {
+            BoundingBox::from_min_max(
+                self.min.as_vec3() * cell_size,
+                self.max.as_vec3() * cell_size,
+            )
+        }
+    }
+
+    /// The maximum cell (inclusive).
+    ///
+    /// Meaningless if the box is empty.
+    #[inline]
+    pub fn max_inclusive(&self) -> IVec3 {
+        self.max.saturating_sub(IVec3::ONE)
+    }
+
+    /// The number of cells along each axis.
+    ///
+    /// Zero if the box is empty. Saturates at [`i32::MAX`] for boxes wider than that.
+    #[inline]
+    pub fn size(&self) -> IVec3 {
+        if self.is_nothing() {
+            IVec3::ZERO
+        } else {
+            self.max.saturating_sub(self.min)
+        }
+    }
+
+    /// The number of cells in the box.
+    ///
+    /// Saturates at [`u64::MAX`], which only very large boxes can reach.
+    pub fn volume(&self) -> u64 {
+        if self.is_nothing() {
+            0
+        } else {
+            let size = (self.max.as_i64vec3() - self.min.as_i64vec3()).as_u64vec3();
+            size.x.saturating_mul(size.y).saturating_mul(size.z)
+        }
+    }
+
+    /// True if the box contains at least one cell.
+    ///
+    /// The opposite of `is_nothing()`.
+    #[inline]
+    pub fn is_something(&self) -> bool {
+        self.min.x < self.max.x && self.min.y < self.max.y && self.min.z < self.max.z
+    }
+
+    /// True if the box contains no cells.
+    ///
+    /// The opposite of `is_something()`.
+    #[inline]
+    pub fn is_nothing(&self) -> bool {
+        !self.is_something()
+    }
+
+    /// Enlarge the box to include this cell.
+    ///
+    /// Cells at [`i32::MAX`] can't be included, as the maximum is exclusive.
+    #[inline]
+    pub fn extend(&mut self, cell: IVec3) {
+        self.min = self.min.min(cell);
+        self.max = self.max.max(cell.saturating_add(IVec3::ONE));
+    }
+
+    /// Returns the smallest box that covers both `self` and `other`.
+    #[must_use]
+    pub fn union(self, other: Self) -> Self {
+        if self.is_nothing() {
+            other
+        } else if other.is_nothing() {
+            self
+        } else {
+            Self {
+                min: self.min.min(other.min),
+                max: self.max.max(other.max),
+            }
+        }
+    }
+
+    /// Returns the cells covered by both `self` and `other`,
+    /// or [`Self::nothing`] if the boxes are disjoint.
+    #[must_use]
+    pub fn intersection(self, other: Self) -> Self {
+        let intersection = Self {
+            min: self.min.max(other.min),
+            max: self.max.min(other.max),
+        };
+        if intersection.is_nothing() {
+            Self::nothing()
+        } else {
+            intersection
+        }
+    }
+
+    /// Returns `true` if the cell is within the box.
+    #[inline]
+    pub fn contains(&self, cell: IVec3) -> bool {
+        self.min.cmple(cell).all() && cell.cmplt(self.max).all()
+    }
+
+    /// Returns `true` if the boxes share at least one cell.
+    #[inline]
+    pub fn intersects(&self, other: &Self) -> bool {
+        self.min.cmplt(other.max).all() && other.min.cmplt(self.max).all()
+    }
+
+    /// Expand with this many cells on each side.
+    ///
+    /// An empty box stays empty, and the box is clamped to the range of [`i32`].
+    #[must_use]
+    pub fn expanded(&self, padding: IVec3) -> Self {
+        if self.is_nothing() {
+            return *self;
+        }
+        Self {
+            min: self.min.saturating_sub(padding),
+            max: self.max.saturating_add(padding),
+        }
+    }
+
+    /// Translate (move) the box by this many cells.
+    ///
+    /// An empty box stays empty, and the box is clamped to the range of [`i32`].
+    #[must_use]
+    pub fn translated(&self, translation: IVec3) -> Self {
+        if self.is_nothing() {
+            return *self;
+        }
+        Self {
+            min: self.min.saturating_add(translation),
+            max: self.max.saturating_add(translation),
+        }
+    }
+
+    /// Iterate over every cell in the box, with `x` changing fastest and `z` slowest.
+    pub fn cells(&self) -> impl Iterator<Item = IVec3> + Clone {
+        let Self { min, max } = *self;
+        (min.z..max.z).flat_map(move |z| {
+            (min.y..max.y).flat_map(move |y| (min.x..max.x).map(move |x| IVec3::new(x, y, z)))
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_ibounding_box() {
+        let bb = IBoundingBox::from_min_max_inclusive(IVec3::new(-1, 0, 2), IVec3::new(1, 1, 2));
+        assert_eq!(bb.max, IVec3::new(2, 2, 3));
+        assert_eq!(bb.max_inclusive(), IVec3::new(1, 1, 2));
+        assert_eq!(bb.size(), IVec3::new(3, 2, 1));
+        assert_eq!(bb.volume(), 6);
+        assert!(bb.contains(IVec3::new(1, 1, 2)));
+        assert!(!bb.contains(IVec3::new(2, 1, 2)));
+
+        let cells: Vec<_> = bb.cells().collect();
+        assert_eq!(cells.len(), 6);
+        assert_eq!(cells[0], bb.min);
+        assert_eq!(cells[1], IVec3::new(0, 0, 2));
+        assert_eq!(cells[5], bb.max_inclusive());
+        assert_eq!(IBoundingBox::from_cells(cells.into_iter()), bb);
+
+        assert_eq!(IBoundingBox::nothing().volume(), 0);
+        assert_eq!(IBoundingBox::nothing().cells().count(), 0);
+        assert_eq!(IBoundingBox::nothing().union(bb), bb);
+        assert_eq!(bb.union(IBoundingBox::nothing()), bb);
+
+        let other = IBoundingBox::from_min_size(IVec3::new(1, 1, 0), IVec3::splat(5));
+        assert!(bb.intersects(&other));
+        assert_eq!(
+            bb.intersection(other),
+            IBoundingBox::from_cell(IVec3::new(1, 1, 2))
+        );
+        assert_eq!(
+            bb.union(other),
+            IBoundingBox::from_min_max_exclusive(IVec3::new(-1, 0, 0), IVec3::new(6, 6, 5))
+        );
+
+        let far = bb.translated(IVec3::splat(10));
+        assert!(!bb.intersects(&far));
+        assert_eq!(bb.intersection(far), IBoundingBox::nothing());
+    }
+
+    #[test]
+    fn test_limits() {
+        let nothing = IBoundingBox::nothing();
+        assert_eq!(nothing.size(), IVec3::ZERO);
+        assert_eq!(nothing.expanded(IVec3::ONE), nothing);
+        assert_eq!(nothing.translated(IVec3::splat(-5)), nothing);
+        assert_eq!(nothing.max_inclusive(), IVec3::MIN);
+
+        let everything = IBoundingBox::from_min_max_exclusive(IVec3::MIN, IVec3::MAX);
+        assert_eq!(everything.size(), IVec3::MAX);
+        assert_eq!(everything.volume(), u64::MAX);
+        assert_eq!(everything.max_inclusive(), IVec3::MAX - IVec3::ONE);
+        assert_eq!(everything.expanded(IVec3::ONE), everything);
+        assert_eq!(
+            everything.translated(IVec3::ONE),
+            IBoundingBox::from_min_max_exclusive(IVec3::MIN + IVec3::ONE, IVec3::MAX)
+        );
+        let wide = IBoundingBox::from_min_max_exclusive(
+            IVec3::new(i32::MIN, 0, 0),
+            IVec3::new(i32::MAX, 2, 3),
+        );
+        assert_eq!(wide.volume(), 6 * u64::from(u32::MAX));
+        assert_eq!(
+            IBoundingBox::from_min_size(IVec3::ONE, IVec3::MAX).max,
+            IVec3::MAX
+        );
+
+        let corner = IBoundingBox::from_cell(IVec3::MAX);
+        assert_eq!(corner.max, IVec3::MAX);
+        assert!(corner.is_nothing());
+        let mut bb = IBoundingBox::nothing();
+        bb.extend(IVec3::MIN);
+        bb.extend(IVec3::MAX);
+        assert_eq!(bb, everything);
+    }
+
+    #[test]
+    fn test_bounding_box_conversion() {
+        let cell_size = Vec3::new(1.0, 2.0, 0.5);
+        let bb = BoundingBox::from_min_max(Vec3::new(-0.5, 0.0, 0.25), Vec3::new(2.0, 3.0, 1.75));
+
+        let outward = IBoundingBox::from_bounding_box_outward(&bb, cell_size);
+        assert_eq!(outward.min, IVec3::new(-1, 0, 0));
+        assert_eq!(outward.max, IVec3::new(2, 2, 4));
+
+        let inward = IBoundingBox::from_bounding_box_inward(&bb, cell_size);
+        assert_eq!(inward.min, IVec3::new(0, 0, 1));
+        assert_eq!(inward.max, IVec3::new(2, 1, 3));
+
+        let outer = outward.to_bounding_box(cell_size);
+        assert!(outer.contains(bb.min) && outer.contains(bb.max));
+        let inner = inward.to_bounding_box(cell_size);
+        assert!(bb.contains(inner.min) && bb.contains(inner.max));
+
+        // A point still touches a cell.
+        let point = BoundingBox::from_min_max(Vec3::ONE, Vec3::ONE);
+        assert_eq!(
+            IBoundingBox::from_bounding_box_outward(&point, Vec3::ONE),
+            IBoundingBox::from_cell(IVec3::ONE)
+        );
+        assert_eq!(
+            IBoundingBox::from_bounding_box_inward(&point, Vec3::ONE),
+            IBoundingBox::nothing()
+        );
+        assert_eq!(
+            IBoundingBox::from_bounding_box_outward(&BoundingBox::nothing(), Vec3::ONE),
+            IBoundingBox::nothing()
+        );
+        assert!(
+            IBoundingBox::nothing()
+                .to_bounding_box(Vec3::ONE)
+                .is_nothing()
+        );
+    }
+}
@@ -4,6 +4,9 @@ use glam::Quat;
 use glam::Vec3;
 use glam::Vec3A;
 
+#[cfg(target_arch = "spirv")]
+use num_traits::Float;
+
 /// An isometric transform represented by translation * rotation.
 ///
 /// An isometric transform conserves distances and angles.
@@ -193,6 +196,89 @@ impl IsoTransform {
     pub fn transform_vector3(&self, v: Vec3) -> Vec3 {
         self.rotation.mul_vec3a(v.into()).into()
     }
+
+    // ------------------------------------------------------------------------
+    // Interpolation:
+
+    /// Interpolates between `self` (at `t = 0`) and `other` (at `t = 1`).
+    ///
+    /// The translation is linearly interpolated and the rotation is spherically interpolated,
+    /// taking the shortest path. This is cheap, but points not at the origin of the transform
+    /// follow curved paths at varying speed. See [`Self::sclerp`] for the alternative.
+    #[inline]
+    #[must_use]
+    pub fn lerp(&self, other: &Self, t: f32) -> Self {
+        Self {
+            rotation: self.rotation.slerp(other.rotation, t),
+            translation: self.translation.lerp(other.translation, t),
+        }
+    }
+
+    /// Screw linear interpolation (`ScLERP`) between `self` (at `t = 0`) and `other` (at `t = 1`).
+    ///
+    /// Any rigid motion is a rotation around some axis combined with a translation along it.
+    /// This interpolates that screw motion at constant speed, so every point moves along a
+    /// helix at constant velocity, and the rotation takes the shortest path.
+    /// This is the same as blending dual quaternions with `ScLERP`.
+    #[must_use]
+    pub fn sclerp(&self, other: &Self, t: f32) -> Self {
+        let relative = self.inverse() * *other;
+        let (rotation, translation) = screw_pow(relative.rotation, 1.0, relative.translation(), t);
+        *self * Self::from_rotation_translation(rotation, translation)
+    }
+}
+
+/// Raises the similarity transform `x -> scale * (rotation * x) + translation` to the power `t`,
+/// returning the rotation and translation at `t`. The scale at `t` is `scale.powf(t)`.
+///
+/// This follows the motion of constant velocity (a screw, or a spiral if scaled) from
+/// the identity at `t = 0` to the transform at `t = 1`, with the rotation taking the shortest path.
+pub(crate) fn screw_pow(rotation: Quat, scale: f32, translation: Vec3, t: f32) -> (Quat, Vec3) {
+    let rotation = if rotation.w < 0.0 {
+        -rotation
+    } else {
+        rotation
+    };
+    let (axis, angle) = rotation.to_axis_angle();
+    let log_scale = scale.ln();
+
+    // The velocity of the motion acts along the axis as multiplication by `log_scale`, and
+    // perpendicular to it as multiplication by the complex number `z = log_scale + i * angle`,
+    // where `i` is a quarter turn around the axis. Integrating it, the translation at `t` is
+    // the full translation multiplied by `(e^(z * t) - 1) / (e^z - 1)`.
+    let exp_m1 = |t: f32| {
+        // e^(z * t) - 1, arranged to avoid cancellation when `z * t` is small.
+        let half_sin = (0.5 * angle * t).sin();
+        let scale_m1 = (log_scale * t).exp_m1();
+        let scale_t = scale_m1 + 1.0;
+        (
+            scale_m1 - 2.0 * scale_t * half_sin * half_sin,
+            scale_t * (angle * t).sin(),
+        )
+    };
+    let (num_re, num_im) = exp_m1(t);
+    let (den_re, den_im) = exp_m1(1.0);
+    let den_length_squared = den_re * den_re + den_im * den_im;
+    let (ratio_re, ratio_im) = if den_length_squared <= f32::EPSILON * f32::EPSILON {
+        (t, 0.0)
+    } else {
+        (
+            (num_re * den_re + num_im * den_im) / den_length_squared,
+            (num_im * den_re - num_re * den_im) / den_length_squared,
+        )
+    };
+    let ratio_along = if log_scale.abs() <= f32::EPSILON {
+        t
+    } else {
+        (log_scale * t).exp_m1() / log_scale.exp_m1()
+    };
+
+    let along = axis * axis.dot(translation);
+    let across = translation - along;
+    (
+        Quat::from_axis_angle(axis, angle * t),
+        along * ratio_along + across * ratio_re + axis.cross(across) * ratio_im,
+    )
 }
 
 /// iso * iso -> iso
@@ -505,4 +591,79 @@ mod test {
             assert_approx_eq_vec3!(transform.transform_point3(point), Vec3::new(0.0, 1.0, -5.0));
         }
     }
+
+    #[test]
+    fn test_lerp() {
+        use std::f32::consts::TAU;
+        let a = IsoTransform::from_translation(Vec3::new(1.0, 0.0, 0.0));
+        let b = IsoTransform::from_rotation_translation(
+            Quat::from_rotation_z(TAU / 4.0),
+            Vec3::new(3.0, 2.0, 0.0),
+        );
+        assert_approx_eq_transform!(a.lerp(&b, 0.0), a);
+        assert_approx_eq_transform!(a.lerp(&b, 1.0), b);
+        assert_approx_eq_transform!(
+            a.lerp(&b, 0.5),
+            IsoTransform::from_rotation_translation(
+                Quat::from_rotation_z(TAU / 8.0),
+                Vec3::new(2.0, 1.0, 0.0)
+            )
+        );
+
+        // Takes the shortest path, regardless of the sign of the quaternion.
+        let b_negated = IsoTransform {
+            rotation: -b.rotation,
+            ..b
+        };
+        assert_approx_eq_transform!(a.lerp(&b_negated, 0.5), a.lerp(&b, 0.5));
+    }
+
+    #[test]
+    fn test_sclerp() {
+        use std::f32::consts::TAU;
+        let t = [
+            IsoTransform::IDENTITY,
+            IsoTransform::from_translation(Vec3::new(1.0, -2.0, 3.0)),
+            IsoTransform::from_rotation_translation(
+                Quat::from_axis_angle(Vec3::new(0.3, -0.5, -0.4).normalize(), 2.5),
+                Vec3::new(0.7, 1.2, 3.4),
+            ),
+            IsoTransform::from_rotation_translation(
+                Quat::from_axis_angle(Vec3::new(-0.8, -0.1, 0.2).normalize(), 1e-4),
+                Vec3::new(-3.6, 4.2, -0.7),
+            ),
+        ];
+        for a in &t {
+            for b in &t {
+                assert_approx_eq_transform!(a.sclerp(b, 0.0), *a);
+                assert_approx_eq_transform!(a.sclerp(b, 1.0), *b);
+                // Halfway twice is the whole way.
+                let half = a.sclerp(b, 0.5);
+                assert_approx_eq_transform!(half * (a.inverse() * half), *b);
+            }
+        }
+
+        // A quarter turn around a vertical axis through `center`, while moving up by 2.
+        let center = Vec3::new(1.0, 2.0, 0.0);
+        let screw = |angle: f32, height: f32| {
+            IsoTransform::from_translation(Vec3::new(0.0, 0.0, height))
+                * IsoTransform::from_rotation_around_point(Quat::from_rotation_z(angle), center)
+        };
+        let a = IsoTransform::IDENTITY;
+        let b = screw(TAU / 4.0, 2.0);
+        for i in 0..=4 {
+            let s = i as f32 / 4.0;
+            assert_approx_eq_transform!(a.sclerp(&b, s), screw(s * TAU / 4.0, s * 2.0));
+        }
+
+        // Unlike lerp, the path is relative to the start, not the origin.
+        let start = IsoTransform::from_rotation_translation(
+            Quat::from_rotation_x(1.0),
+            Vec3::new(5.0, -1.0, 2.0),
+        );
+        assert_approx_eq_transform!(
+            (start * a).sclerp(&(start * b), 0.5),
+            start * screw(TAU / 8.0, 1.0)
+        );
+    }
 }
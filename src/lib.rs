@@ -5,20 +5,65 @@
 
 mod affine3;
 mod bounding_box;
+mod bounding_rect;
+#[cfg(not(target_arch = "spirv"))]
+mod bounding_sphere;
+#[cfg(not(target_arch = "spirv"))]
+mod bvh;
+#[cfg(not(target_arch = "spirv"))]
+mod capsule3;
+mod circle;
 #[cfg(not(target_arch = "spirv"))]
 mod color_rgba8;
 mod conformal;
+#[cfg(not(target_arch = "spirv"))]
+mod convex_hull;
+#[cfg(not(target_arch = "spirv"))]
+mod convex_polyhedron;
+#[cfg(not(target_arch = "spirv"))]
+mod dbounding_box;
+#[cfg(not(target_arch = "spirv"))]
+mod diso_transform;
+#[cfg(not(target_arch = "spirv"))]
+mod dplane3;
+#[cfg(not(target_arch = "spirv"))]
+mod dray3;
 mod dual_quat;
+#[cfg(not(target_arch = "spirv"))]
+mod dynamic_aabb_tree;
 mod fixed;
 mod float_ext;
+#[cfg(not(target_arch = "spirv"))]
+mod frustum;
+#[cfg(not(target_arch = "spirv"))]
+mod gjk;
+#[cfg(not(target_arch = "spirv"))]
+mod ibounding_box;
 mod iso_transform;
+mod line2;
 mod mat3_ext;
 #[cfg(not(target_arch = "spirv"))]
 mod mesh_gen;
+#[cfg(not(target_arch = "spirv"))]
+mod obb3;
 mod plane3;
 #[cfg(not(target_arch = "spirv"))]
 mod quat_ext;
+mod ray2;
 mod ray3;
+#[cfg(not(target_arch = "spirv"))]
+mod raycast;
+mod segment2;
+#[cfg(not(target_arch = "spirv"))]
+mod segment3;
+#[cfg(not(target_arch = "spirv"))]
+mod support;
+#[cfg(test)]
+mod test_utils;
+#[cfg(not(target_arch = "spirv"))]
+mod tri_mesh;
+#[cfg(not(target_arch = "spirv"))]
+mod triangle;
 mod utils;
 mod vec2_ext;
 mod vec3_ext;
@@ -26,25 +71,66 @@ mod vec4_ext;
 
 pub use self::affine3::*;
 pub use self::bounding_box::*;
+pub use self::bounding_rect::*;
+pub use self::circle::*;
 pub use self::conformal::*;
 pub use self::dual_quat::*;
 pub use self::fixed::*;
 pub use self::float_ext::*;
 pub use self::iso_transform::*;
+pub use self::line2::*;
 pub use self::mat3_ext::*;
 pub use self::plane3::*;
+pub use self::ray2::*;
 pub use self::ray3::*;
+pub use self::segment2::*;
 pub use self::utils::*;
 pub use self::vec2_ext::*;
 pub use self::vec3_ext::*;
 pub use self::vec4_ext::*;
 
+#[cfg(not(target_arch = "spirv"))]
+pub use bounding_sphere::*;
+#[cfg(not(target_arch = "spirv"))]
+pub use bvh::*;
+#[cfg(not(target_arch = "spirv"))]
+pub use capsule3::*;
 #[cfg(not(target_arch = "spirv"))]
 pub use color_rgba8::*;
 #[cfg(not(target_arch = "spirv"))]
+pub use convex_hull::*;
+#[cfg(not(target_arch = "spirv"))]
+pub use convex_polyhedron::*;
+#[cfg(not(target_arch = "spirv"))]
+pub use dbounding_box::*;
+#[cfg(not(target_arch = "spirv"))]
+pub use diso_transform::*;
+#[cfg(not(target_arch = "spirv"))]
+pub use dplane3::*;
+#[cfg(not(target_arch = "spirv"))]
+pub use dray3::*;
+#[cfg(not(target_arch = "spirv"))]
+pub use dynamic_aabb_tree::*;
+#[cfg(not(target_arch = "spirv"))]
+pub use frustum::*;
+#[cfg(not(target_arch = "spirv"))]
+pub use gjk::*;
+#[cfg(not(target_arch = "spirv"))]
+pub use ibounding_box::*;
+#[cfg(not(target_arch = "spirv"))]
 pub use mesh_gen::*;
 #[cfg(not(target_arch = "spirv"))]
+pub use obb3::*;
+#[cfg(not(target_arch = "spirv"))]
 pub use quat_ext::*;
+#[cfg(not(target_arch = "spirv"))]
+pub use raycast::*;
+#[cfg(not(target_arch = "spirv"))]
+pub use segment3::*;
+#[cfg(not(target_arch = "spirv"))]
+pub use support::*;
+#[cfg(not(target_arch = "spirv"))]
+pub use tri_mesh::*;
 
 /// Prelude module with extension traits
 pub mod prelude {
@@ -58,6 +144,8 @@ pub mod prelude {
 
     #[cfg(not(target_arch = "spirv"))]
     pub use crate::QuatExt;
+    #[cfg(not(target_arch = "spirv"))]
+    pub use crate::Raycast;
 }
 
 // Re-export main glam types.
@@ -75,6 +163,7 @@ pub use glam::uvec2;
 pub use glam::uvec3;
 pub use glam::uvec4;
 // f32
+pub use glam::Affine2;
 pub use glam::Affine3A;
 pub use glam::Mat2;
 pub use glam::Mat3;
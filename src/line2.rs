@@ -0,0 +1,215 @@
+use crate::Vec2;
+
+#[cfg(target_arch = "spirv")]
+use num_traits::Float;
+
+/// Which side of a line a point is on, see [`Line2::side`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Side {
+    /// The side the normal points towards.
+    Front,
+    /// On the line (within some epsilon).
+    On,
+    /// The side the normal points away from.
+    Back,
+}
+
+/// A 2-dimensional line, the 2D equivalent of [`crate::Plane3`].
+///
+/// Represented by a normal and the signed distance from the origin to the line.
+///
+/// A point `point` is on the line when `line.normal.dot(point) + line.d = 0`.
+#[derive(Copy, Clone, PartialEq)]
+#[cfg_attr(not(target_arch = "spirv"), derive(Debug))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "speedy", derive(speedy::Writable, speedy::Readable))]
+pub struct Line2 {
+    /// Normal vector
+    pub normal: Vec2,
+    /// Distance
+    pub d: f32,
+}
+
+impl Line2 {
+    /// The X axis, with normal = +Y
+    pub const X: Self = Self {
+        normal: Vec2::Y,
+        d: 0.0,
+    };
+    /// The Y axis, with normal = +X
+    pub const Y: Self = Self {
+        normal: Vec2::X,
+        d: 0.0,
+    };
+
+    /// Lines whose normals are closer to parallel than this, measured as the sine of the angle
+    /// between them, are treated as parallel by [`Self::intersect_line`].
+    pub const PARALLEL_EPSILON: f32 = 1e-6;
+
+    /// From the line normal and a distance `d` so that for all points on the line:
+    /// `normal.dot(point) + d = 0`.
+    #[inline]
+    pub fn from_normal_dist(normal: Vec2, d: f32) -> Self {
+        Self { normal, d }
+    }
+
+    /// From the line normal and a point on the line.
+    #[inline]
+    pub fn from_normal_point(normal: Vec2, point: Vec2) -> Self {
+        Self {
+            normal,
+            d: -normal.dot(point),
+        }
+    }
+
+    /// From two points on the line.
+    ///
+    /// The normal is normalized, and points to the left when going from `a` to `b`
+    /// (counter-clockwise, with Y up).
+    /// If the points are the same, the normal is zero.
+    #[inline]
+    pub fn from_points(a: Vec2, b: Vec2) -> Self {
+        Self::from_normal_point((b - a).perp().normalize_or_zero(), a)
+    }
+
+    /// Get normalized line
+    #[inline]
+    #[must_use]
+    pub fn normalized(&self) -> Self {
+        let inv_len = self.normal.length_recip();
+        Self {
+            normal: self.normal * inv_len,
+            d: self.d * inv_len,
+        }
+    }
+
+    /// The direction along the line, which is the normal rotated clockwise.
+    ///
+    /// For a line created with [`Self::from_points`], this goes from `a` towards `b`.
+    #[inline]
+    pub fn dir(&self) -> Vec2 {
+        Vec2::new(self.normal.y, -self.normal.x)
+    }
+
+    /// Computes the distance between the line and the point p.
+    /// The returned distance is only correct if the line is normalized or the distance is zero.
+    #[inline]
+    pub fn distance(&self, p: Vec2) -> f32 {
+        self.normal.dot(p) + self.d
+    }
+
+    /// Returns the point on the line closest to `p`.
+    #[inline]
+    pub fn project_point(&self, p: Vec2) -> Vec2 {
+        p - self.normal * (self.distance(p) / self.normal.length_squared())
+    }
+
+    /// The same line, but facing the other way.
+    #[inline]
+    #[must_use]
+    pub fn flipped(&self) -> Self {
+        Self {
+            normal: -self.normal,
+            d: -self.d,
+        }
+    }
+
+    /// Which side of the line the point is on.
+    ///
+    /// Points within `epsilon` of the line are [`Side::On`] it.
+    #[inline]
+    pub fn side(&self, p: Vec2, epsilon: f32) -> Side {
+        let distance = self.distance(p);
+        if distance > epsilon {
+            Side::Front
+        } else if distance < -epsilon {
+            Side::Back
+        } else {
+            Side::On
+        }
+    }
+
+    /// The point where two lines intersect.
+    ///
+    /// Returns [`None`] if the lines are parallel, or so close to parallel that the
+    /// sine of the angle between their normals is below [`Self::PARALLEL_EPSILON`].
+    #[cfg(not(target_arch = "spirv"))] // TODO: large Options in rust-gpu
+    pub fn intersect_line(&self, other: &Self) -> Option<Vec2> {
+        let (n1, n2) = (self.normal, other.normal);
+        let det = n1.perp_dot(n2);
+        if det.abs() <= Self::PARALLEL_EPSILON * n1.length() * n2.length() {
+            return None;
+        }
+        Some(
+            Vec2::new(
+                other.d * n1.y - self.d * n2.y,
+                self.d * n2.x - other.d * n1.x,
+            ) / det,
+        )
+    }
+
+    /// The points where the line crosses the edge of the circle, in the order of [`Self::dir`].
+    ///
+    /// The points are the same if the line just touches the circle.
+    /// Returns [`None`] if the line misses the circle.
+    #[cfg(not(target_arch = "spirv"))] // TODO: large Options in rust-gpu
+    pub fn intersect_circle(&self, circle: &crate::Circle) -> Option<(Vec2, Vec2)> {
+        let line = self.normalized();
+        let distance = line.distance(circle.center);
+        let half_chord_squared = circle.radius * circle.radius - distance * distance;
+        if half_chord_squared < 0.0 {
+            return None;
+        }
+        let foot = circle.center - line.normal * distance;
+        let offset = line.dir() * half_chord_squared.sqrt();
+        Some((foot - offset, foot + offset))
+    }
+
+    /// True if every value is finite
+    #[inline]
+    pub fn is_finite(&self) -> bool {
+        self.normal.is_finite() && self.d.is_finite()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::Circle;
+
+    #[test]
+    fn test_line2() {
+        #![allow(clippy::float_cmp)]
+        let line = Line2::from_points(Vec2::new(0.0, 1.0), Vec2::new(2.0, 1.0));
+        assert_eq!(line.normal, Vec2::Y);
+        assert_eq!(line.dir(), Vec2::X);
+        assert_eq!(line.distance(Vec2::new(5.0, 3.0)), 2.0);
+        assert_eq!(line.project_point(Vec2::new(5.0, 3.0)), Vec2::new(5.0, 1.0));
+        assert_eq!(line.side(Vec2::new(5.0, 3.0), 1e-6), Side::Front);
+        assert_eq!(line.side(Vec2::new(5.0, 0.0), 1e-6), Side::Back);
+        assert_eq!(line.side(Vec2::new(-5.0, 1.0), 1e-6), Side::On);
+        assert_eq!(line.flipped().side(Vec2::new(5.0, 3.0), 1e-6), Side::Back);
+
+        let diagonal = Line2::from_points(Vec2::ZERO, Vec2::ONE);
+        assert!(
+            line.intersect_line(&diagonal)
+                .unwrap()
+                .abs_diff_eq(Vec2::ONE, 1e-6)
+        );
+        assert_eq!(line.intersect_line(&Line2::X), None);
+        assert_eq!(Line2::X.intersect_line(&Line2::Y), Some(Vec2::ZERO));
+
+        let circle = Circle::from_center_radius(Vec2::new(1.0, 0.0), 2.0);
+        let (p, q) = Line2::X.intersect_circle(&circle).unwrap();
+        assert_eq!((p, q), (Vec2::new(-1.0, 0.0), Vec2::new(3.0, 0.0)));
+        let (p, q) = line.intersect_circle(&circle).unwrap();
+        assert!(p.abs_diff_eq(Vec2::new(1.0 - 3.0_f32.sqrt(), 1.0), 1e-6));
+        assert!(q.abs_diff_eq(Vec2::new(1.0 + 3.0_f32.sqrt(), 1.0), 1e-6));
+        assert_eq!(
+            line.flipped().intersect_circle(&circle).map(|(p, _)| p),
+            Some(q)
+        );
+        let far = Line2::from_normal_point(Vec2::Y, Vec2::new(0.0, 3.0));
+        assert_eq!(far.intersect_circle(&circle), None);
+    }
+}
@@ -1,7 +1,25 @@
 use crate::IsoTransform;
+use crate::Plane3;
+use crate::Segment3;
 use crate::Vec3;
+use std::collections::HashMap;
+use std::collections::HashSet;
 use std::ops::Range;
 
+/// The result of [`MeshGen::slice`].
+pub struct MeshSlice {
+    /// The part of the mesh in front of the plane.
+    pub front: MeshGen,
+    /// The part of the mesh behind the plane.
+    pub back: MeshGen,
+    /// The segments where the plane cuts through triangles.
+    ///
+    /// For a closed mesh, these form closed loops that are counter-clockwise when seen from
+    /// behind the plane, i.e. facing the same way as a cap for the hole in the front mesh.
+    /// Reverse them to cap the back mesh.
+    pub cap_outline: Vec<Segment3>,
+}
+
 /// Raw mesh generator. Only generates positions, normals and an index buffer.
 ///
 /// Composable - to create a composite mesh, just repeatedly call the various
@@ -14,6 +32,13 @@ pub struct MeshGen {
     pub indices: Vec<u32>,
 }
 
+/// Identifies a directed edge by the positions of its end points.
+fn edge_key(a: Vec3, b: Vec3) -> [u32; 6] {
+    let [ax, ay, az] = a.to_array().map(f32::to_bits);
+    let [bx, by, bz] = b.to_array().map(f32::to_bits);
+    [ax, ay, az, bx, by, bz]
+}
+
 fn transform_points(points: &mut [Vec3], transform: IsoTransform) {
     if transform != IsoTransform::IDENTITY {
         for point in points {
@@ -221,4 +246,241 @@ impl MeshGen {
 
         out_range
     }
+
+    /// Cut the mesh in two by a plane.
+    ///
+    /// Triangles crossing the plane are split, with new vertices where their edges cross it.
+    /// Positions and normals of the new vertices are interpolated, and the new vertices are
+    /// shared between neighboring triangles. Triangles lying in the plane end up in the front mesh.
+    ///
+    /// Normals are only kept if there is one per position.
+    pub fn slice(&self, plane: &Plane3) -> MeshSlice {
+        let has_normals = self.normals.len() == self.positions.len();
+
+        // The original vertices followed by the new ones on the plane.
+        let mut positions = self.positions.clone();
+        let mut normals = if has_normals {
+            self.normals.clone()
+        } else {
+            vec![]
+        };
+        let mut dist: Vec<f32> = positions.iter().map(|&p| plane.distance(p)).collect();
+        let mut crossings: HashMap<(u32, u32), u32> = HashMap::new();
+
+        let mut front_indices = vec![];
+        let mut back_indices = vec![];
+        let mut cap_outline = vec![];
+        // Edges of the original mesh lying in the plane, as seen from the triangle in front of
+        // the plane and from the one behind it. Only edges with triangles on both sides are cut.
+        // They are matched by position, as meshes such as cubes don't share vertices between sides.
+        let mut front_edges_on_plane = vec![];
+        let mut back_edges_on_plane = HashSet::new();
+        let is_original = |i: u32| (i as usize) < self.positions.len();
+
+        for triangle in self.indices.chunks_exact(3) {
+            let d = [0, 1, 2].map(|i| dist[triangle[i] as usize]);
+            // Triangles lying in the plane don't touch the cut.
+            if d.iter().all(|&d| 0.0 < d) || d.iter().all(|&d| d == 0.0) {
+                front_indices.extend_from_slice(triangle);
+                continue;
+            }
+            if d.iter().all(|&d| d < 0.0) {
+                back_indices.extend_from_slice(triangle);
+                continue;
+            }
+
+            // Each vertex with its position, so that we can add new vertices while splitting.
+            let polygon = [0, 1, 2].map(|i| (triangle[i], positions[triangle[i] as usize]));
+            let (front, back) = plane.split_convex_polygon_with(
+                &polygon,
+                |&(_, p)| p,
+                |&(a, _), &(b, _), t| {
+                    // Both triangles along an edge cross it at the same new vertex.
+                    let crossing = *crossings.entry((a.min(b), a.max(b))).or_insert_with(|| {
+                        let (a, b) = (a as usize, b as usize);
+                        positions.push(positions[a].lerp(positions[b], t));
+                        if has_normals {
+                            normals.push(normals[a].lerp(normals[b], t).normalize_or_zero());
+                        }
+                        dist.push(0.0);
+                        (positions.len() - 1) as u32
+                    });
+                    (crossing, positions[crossing as usize])
+                },
+            );
+            let front: Vec<u32> = front.into_iter().map(|(i, _)| i).collect();
+            let back: Vec<u32> = back.into_iter().map(|(i, _)| i).collect();
+
+            // The cut is where the front piece runs along the plane.
+            // The cap goes the other way around it.
+            for i in 0..front.len() {
+                let (a, b) = (front[i], front[(i + 1) % front.len()]);
+                if dist[a as usize] == 0.0 && dist[b as usize] == 0.0 {
+                    if is_original(a) && is_original(b) {
+                        front_edges_on_plane.push((a, b));
+                    } else {
+                        cap_outline
+                            .push(Segment3::new(positions[b as usize], positions[a as usize]));
+                    }
+                    break;
+                }
+            }
+            for i in 0..back.len() {
+                let (a, b) = (back[i], back[(i + 1) % back.len()]);
+                if dist[a as usize] == 0.0
+                    && dist[b as usize] == 0.0
+                    && is_original(a)
+                    && is_original(b)
+                {
+                    back_edges_on_plane
+                        .insert(edge_key(positions[a as usize], positions[b as usize]));
+                }
+            }
+
+            for (piece, indices) in [(front, &mut front_indices), (back, &mut back_indices)] {
+                for i in 2..piece.len() {
+                    indices.extend([piece[0], piece[i - 1], piece[i]]);
+                }
+            }
+        }
+
+        for (a, b) in front_edges_on_plane {
+            let (a, b) = (positions[a as usize], positions[b as usize]);
+            if back_edges_on_plane.contains(&edge_key(b, a)) {
+                cap_outline.push(Segment3::new(b, a));
+            }
+        }
+
+        let compact = |indices: Vec<u32>| {
+            let mut mesh = Self::new();
+            let mut remap = vec![u32::MAX; positions.len()];
+            for i in indices {
+                let i = i as usize;
+                if remap[i] == u32::MAX {
+                    remap[i] = mesh.positions.len() as u32;
+                    mesh.positions.push(positions[i]);
+                    if has_normals {
+                        mesh.normals.push(normals[i]);
+                    }
+                }
+                mesh.indices.push(remap[i]);
+            }
+            mesh
+        };
+
+        MeshSlice {
+            front: compact(front_indices),
+            back: compact(back_indices),
+            cap_outline,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_slice() {
+        let mut mesh = MeshGen::new();
+        mesh.push_sphere(1.0, 12, 8, IsoTransform::IDENTITY);
+
+        let plane =
+            Plane3::from_normal_point(Vec3::new(0.2, 1.0, 0.1).normalize(), Vec3::splat(0.1));
+        let slice = mesh.slice(&plane);
+
+        for part in [&slice.front, &slice.back] {
+            assert_eq!(part.normals.len(), part.positions.len());
+            assert_eq!(part.indices.len() % 3, 0);
+            assert!(
+                part.indices
+                    .iter()
+                    .all(|&i| (i as usize) < part.positions.len())
+            );
+        }
+        for &p in &slice.front.positions {
+            assert!(plane.distance(p) >= -1e-5);
+        }
+        for &p in &slice.back.positions {
+            assert!(plane.distance(p) <= 1e-5);
+        }
+
+        // The outline is closed: every end point is the start of another segment.
+        assert!(!slice.cap_outline.is_empty());
+        for segment in &slice.cap_outline {
+            assert!(plane.distance(segment.a).abs() < 1e-5);
+            assert!(slice.cap_outline.iter().any(|other| other.a == segment.b));
+        }
+
+        // Counter-clockwise when seen from behind the plane.
+        let area: Vec3 = slice.cap_outline.iter().map(|s| s.a.cross(s.b)).sum();
+        assert!(area.dot(-plane.normal) > 0.0);
+
+        // Entirely on one side.
+        let slice = mesh.slice(&Plane3::from_normal_point(
+            Vec3::Y,
+            Vec3::new(0.0, -2.0, 0.0),
+        ));
+        assert_eq!(slice.front.indices.len(), mesh.indices.len());
+        assert!(slice.back.indices.is_empty());
+        assert!(slice.cap_outline.is_empty());
+    }
+
+    #[test]
+    fn test_slice_through_vertices() {
+        let mut mesh = MeshGen::new();
+        mesh.push_cube(Vec3::ONE, IsoTransform::IDENTITY);
+
+        // Through two opposite edges of the cube, along the diagonal of the top face,
+        // and across the diagonal of the bottom face.
+        let plane = Plane3::from_normal_point(Vec3::new(1.0, -1.0, 0.0).normalize(), Vec3::ZERO);
+        let slice = mesh.slice(&plane);
+        // Only the two triangles of the bottom face are cut in two.
+        assert_eq!(slice.front.indices.len() + slice.back.indices.len(), 3 * 14);
+
+        // A single loop: every end point is the start of exactly one other segment.
+        let outline = &slice.cap_outline;
+        assert_eq!(outline.len(), 5);
+        for segment in outline {
+            assert_eq!(plane.distance(segment.a), 0.0);
+            assert_eq!(
+                outline.iter().filter(|other| other.a == segment.b).count(),
+                1
+            );
+        }
+        let mut p = outline[0].a;
+        for _ in 0..outline.len() {
+            p = outline.iter().find(|segment| segment.a == p).unwrap().b;
+        }
+        assert_eq!(p, outline[0].a);
+
+        // The rectangle through the cube, counter-clockwise when seen from behind.
+        let area: Vec3 = outline.iter().map(|s| s.a.cross(s.b)).sum::<Vec3>() / 2.0;
+        assert!((area.dot(-plane.normal) - 4.0 * 2.0_f32.sqrt()).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_slice_touching() {
+        let mut mesh = MeshGen::new();
+        mesh.push_cube(Vec3::ONE, IsoTransform::IDENTITY);
+
+        // Along a face, from either side.
+        let bottom = Plane3::from_normal_point(Vec3::Y, Vec3::NEG_Y);
+        for plane in [bottom, bottom.flipped()] {
+            let slice = mesh.slice(&plane);
+            assert_eq!(slice.front.indices.len() + slice.back.indices.len(), 36);
+            assert!(slice.cap_outline.is_empty());
+        }
+        assert!(mesh.slice(&bottom).back.indices.is_empty());
+
+        // Along an edge, from either side.
+        let edge = Plane3::from_normal_point(Vec3::new(1.0, 1.0, 0.0).normalize(), -Vec3::ONE);
+        for plane in [edge, edge.flipped()] {
+            let slice = mesh.slice(&plane);
+            assert_eq!(slice.front.indices.len() + slice.back.indices.len(), 36);
+            assert!(slice.cap_outline.is_empty());
+        }
+        assert!(mesh.slice(&edge).back.indices.is_empty());
+        assert!(mesh.slice(&edge.flipped()).front.indices.is_empty());
+    }
 }
@@ -0,0 +1,400 @@
+use crate::BoundingBox;
+use crate::Conformal3;
+use crate::IsoTransform;
+use crate::Mat3;
+use crate::Quat;
+use crate::Vec3;
+
+/// A 3-dimensional oriented bounding box.
+///
+/// Represented by a center, the half size along each of its local axes,
+/// and a rotation from the local axes to world space.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "speedy", derive(speedy::Writable, speedy::Readable))]
+pub struct Obb3 {
+    /// Center of the box.
+    pub center: Vec3,
+    /// Half the size of the box along each local axis (similar to a radius).
+    pub half_size: Vec3,
+    /// Rotation from the local axes of the box to world space, normalized.
+    pub rotation: Quat,
+}
+
+impl Obb3 {
+    /// Create an oriented bounding box from its center, half size and rotation.
+    #[inline]
+    pub fn from_center_half_size_rotation(center: Vec3, half_size: Vec3, rotation: Quat) -> Self {
+        Self {
+            center,
+            half_size,
+            rotation,
+        }
+    }
+
+    /// An oriented bounding box covering exactly the same volume as `bb`.
+    ///
+    /// Requires a well-formed box for the result to be valid.
+    #[inline]
+    pub fn from_bounding_box(bb: &BoundingBox) -> Self {
+        Self::from_center_half_size_rotation(bb.center(), bb.half_size(), Quat::IDENTITY)
+    }
+
+    /// The box `bb` after it has been transformed by `m`.
+    ///
+    /// Unlike [`BoundingBox::transform_iso`], this is an exact fit.
+    #[inline]
+    pub fn from_bounding_box_iso(bb: &BoundingBox, m: &IsoTransform) -> Self {
+        Self::from_bounding_box(bb).transform_iso(m)
+    }
+
+    /// The box `bb` after it has been transformed by `m`.
+    ///
+    /// Unlike [`BoundingBox::transform_conformal3`], this is an exact fit.
+    #[inline]
+    pub fn from_bounding_box_conformal3(bb: &BoundingBox, m: &Conformal3) -> Self {
+        Self::from_bounding_box(bb).transform_conformal3(m)
+    }
+
+    /// Fit an oriented bounding box to a set of points.
+    ///
+    /// The axes of the box are the principal components of the points, which gives a
+    /// tight (but not necessarily minimal) box for most point sets.
+    ///
+    /// Returns [`None`] if `points` is empty.
+    pub fn from_points(points: &[Vec3]) -> Option<Self> {
+        if points.is_empty() {
+            return None;
+        }
+
+        let mean = points.iter().copied().sum::<Vec3>() / points.len() as f32;
+        let covariance = points.iter().fold(Mat3::ZERO, |cov, &p| {
+            let d = p - mean;
+            cov + Mat3::from_cols(d * d.x, d * d.y, d * d.z)
+        });
+
+        let eigenvectors = symmetric_eigenvectors(covariance);
+        let x_axis = eigenvectors.x_axis.normalize();
+        let y_axis = eigenvectors.y_axis.normalize();
+        // Make sure we end up with a proper rotation and not a reflection.
+        let z_axis = x_axis.cross(y_axis);
+        let rotation = Quat::from_mat3(&Mat3::from_cols(x_axis, y_axis, z_axis)).normalize();
+
+        let inv_rotation = rotation.inverse();
+        let local_bb = BoundingBox::from_points(points.iter().map(|&p| inv_rotation * p));
+
+        Some(Self::from_center_half_size_rotation(
+            rotation * local_bb.center(),
+            local_bb.half_size(),
+            rotation,
+        ))
+    }
+
+    /// The three local axes of the box in world space, normalized.
+    #[inline]
+    pub fn axes(&self) -> [Vec3; 3] {
+        let m = Mat3::from_quat(self.rotation);
+        [m.x_axis, m.y_axis, m.z_axis]
+    }
+
+    /// Returns the 3D axis size of the box along its local axes.
+    #[inline]
+    pub fn size(&self) -> Vec3 {
+        2.0 * self.half_size
+    }
+
+    /// Only correct for positively sized boxes.
+    pub fn volume(&self) -> f32 {
+        let s = self.size();
+        s.x * s.y * s.z
+    }
+
+    /// The eight corners of this box.
+    ///
+    /// Uses the same order as [`BoundingBox::corners`] in the local space of the box.
+    pub fn corners(&self) -> [Vec3; 8] {
+        BoundingBox::from_min_max(-self.half_size, self.half_size)
+            .corners()
+            .map(|corner| self.center + self.rotation * corner)
+    }
+
+    /// Returns `true` if the point is within (or on the edge of) the box.
+    #[must_use]
+    pub fn contains(&self, point: Vec3) -> bool {
+        let local = self.rotation.inverse() * (point - self.center);
+        local.abs().cmple(self.half_size).all()
+    }
+
+    /// The smallest axis-aligned [`BoundingBox`] containing this box.
+    pub fn to_bounding_box(&self) -> BoundingBox {
+        // Inspired by:
+        // https://zeux.io/2010/10/17/aabb-from-obb-with-component-wise-abs
+        let m = Mat3::from_quat(self.rotation);
+        let abs_m = Mat3::from_cols(m.x_axis.abs(), m.y_axis.abs(), m.z_axis.abs());
+        let half_size = abs_m * self.half_size;
+        BoundingBox::from_min_max(self.center - half_size, self.center + half_size)
+    }
+
+    /// Returns `true` if the two boxes overlap (or touch).
+    ///
+    /// Uses the separating axis theorem, testing the 15 potentially separating axes.
+    pub fn intersects_obb(&self, other: &Self) -> bool {
+        // Based on "Real-Time Collision Detection" by Christer Ericson, section 4.4.1.
+
+        // Counteracts arithmetic errors when two edges are (nearly) parallel
+        // and their cross product is (near) zero.
+        const EPSILON: f32 = 1e-6;
+
+        let a_axes = self.axes();
+        let b_axes = other.axes();
+        let ea = self.half_size;
+        let eb = other.half_size;
+
+        // `other` rotation expressed in the frame of `self`.
+        let mut r = [[0.0; 3]; 3];
+        let mut abs_r = [[0.0; 3]; 3];
+        for i in 0..3 {
+            for j in 0..3 {
+                r[i][j] = a_axes[i].dot(b_axes[j]);
+                abs_r[i][j] = r[i][j].abs() + EPSILON;
+            }
+        }
+
+        // Translation expressed in the frame of `self`.
+        let t = other.center - self.center;
+        let t = [t.dot(a_axes[0]), t.dot(a_axes[1]), t.dot(a_axes[2])];
+
+        // The axes of `self`.
+        for i in 0..3 {
+            let ra = ea[i];
+            let rb = eb[0] * abs_r[i][0] + eb[1] * abs_r[i][1] + eb[2] * abs_r[i][2];
+            if t[i].abs() > ra + rb {
+                return false;
+            }
+        }
+
+        // The axes of `other`.
+        for j in 0..3 {
+            let ra = ea[0] * abs_r[0][j] + ea[1] * abs_r[1][j] + ea[2] * abs_r[2][j];
+            let rb = eb[j];
+            if (t[0] * r[0][j] + t[1] * r[1][j] + t[2] * r[2][j]).abs() > ra + rb {
+                return false;
+            }
+        }
+
+        // The cross products of each pair of axes.
+        for i in 0..3 {
+            let (i1, i2) = ((i + 1) % 3, (i + 2) % 3);
+            for j in 0..3 {
+                let (j1, j2) = ((j + 1) % 3, (j + 2) % 3);
+                let ra = ea[i1] * abs_r[i2][j] + ea[i2] * abs_r[i1][j];
+                let rb = eb[j1] * abs_r[i][j2] + eb[j2] * abs_r[i][j1];
+                if (t[i2] * r[i1][j] - t[i1] * r[i2][j]).abs() > ra + rb {
+                    return false;
+                }
+            }
+        }
+
+        true
+    }
+
+    /// Returns `true` if this box overlaps (or touches) the axis-aligned box `bb`.
+    pub fn intersects_bounding_box(&self, bb: &BoundingBox) -> bool {
+        bb.is_something() && self.intersects_obb(&Self::from_bounding_box(bb))
+    }
+
+    /// Return this box after it has been transformed.
+    #[must_use]
+    pub fn transform_iso(&self, m: &IsoTransform) -> Self {
+        Self {
+            center: m.transform_point3(self.center),
+            half_size: self.half_size,
+            rotation: m.rotation() * self.rotation,
+        }
+    }
+
+    /// Return this box after it has been transformed.
+    #[must_use]
+    pub fn transform_conformal3(&self, m: &Conformal3) -> Self {
+        Self {
+            center: m.transform_point3(self.center),
+            half_size: self.half_size * m.scale(),
+            rotation: m.rotation() * self.rotation,
+        }
+    }
+}
+
+impl From<BoundingBox> for Obb3 {
+    #[inline]
+    fn from(bb: BoundingBox) -> Self {
+        Self::from_bounding_box(&bb)
+    }
+}
+
+/// Returns the eigenvectors of a symmetric matrix as the columns of the returned matrix.
+///
+/// Uses the cyclic Jacobi eigenvalue algorithm.
+#[allow(clippy::needless_range_loop)] // indexing reads better here
+fn symmetric_eigenvectors(m: Mat3) -> Mat3 {
+    const MAX_SWEEPS: usize = 32;
+
+    let mut a = m.to_cols_array_2d();
+    let mut v = Mat3::IDENTITY.to_cols_array_2d();
+
+    for _ in 0..MAX_SWEEPS {
+        let off_diagonal = a[0][1].abs() + a[0][2].abs() + a[1][2].abs();
+        if off_diagonal <= f32::EPSILON * (a[0][0].abs() + a[1][1].abs() + a[2][2].abs()) {
+            break;
+        }
+
+        for (p, q) in [(0, 1), (0, 2), (1, 2)] {
+            if a[p][q] == 0.0 {
+                continue;
+            }
+
+            // Rotate in the `p`-`q` plane so that `a[p][q]` becomes zero.
+            let theta = (a[q][q] - a[p][p]) / (2.0 * a[p][q]);
+            let t = theta.signum() / (theta.abs() + (theta * theta + 1.0).sqrt());
+            let c = (t * t + 1.0).sqrt().recip();
+            let s = t * c;
+
+            for k in 0..3 {
+                let (akp, akq) = (a[k][p], a[k][q]);
+                a[k][p] = c * akp - s * akq;
+                a[k][q] = s * akp + c * akq;
+            }
+            for k in 0..3 {
+                let (apk, aqk) = (a[p][k], a[q][k]);
+                a[p][k] = c * apk - s * aqk;
+                a[q][k] = s * apk + c * aqk;
+            }
+            for k in 0..3 {
+                let (vkp, vkq) = (v[k][p], v[k][q]);
+                v[k][p] = c * vkp - s * vkq;
+                v[k][q] = s * vkp + c * vkq;
+            }
+        }
+    }
+
+    // `v` is indexed as `v[row][column]` above, so transpose it back.
+    Mat3::from_cols_array_2d(&v).transpose()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::f32::consts::FRAC_PI_4;
+
+    #[test]
+    fn test_to_bounding_box() {
+        let bb = BoundingBox::from_center_size(Vec3::ZERO, Vec3::ONE);
+        let m = IsoTransform::from_rotation_translation(Quat::from_rotation_z(FRAC_PI_4), Vec3::X);
+        let obb = Obb3::from_bounding_box_iso(&bb, &m);
+
+        let aabb = obb.to_bounding_box();
+        let expected = bb.transform_iso(&m);
+        assert!(aabb.min.abs_diff_eq(expected.min, 1e-6));
+        assert!(aabb.max.abs_diff_eq(expected.max, 1e-6));
+
+        assert!(obb.contains(Vec3::X));
+        assert!(obb.contains(Vec3::X + Vec3::new(0.7, 0.0, 0.0)));
+        assert!(!obb.contains(Vec3::X + Vec3::new(0.5, 0.5, 0.0)));
+        for corner in obb.corners() {
+            assert!(((corner - Vec3::X).truncate().length() - 0.5_f32.sqrt()).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn test_transform_conformal3() {
+        let bb = BoundingBox::from_center_size(Vec3::ONE, Vec3::ONE);
+        let m = Conformal3::from_scale_rotation_translation(
+            2.0,
+            Quat::from_rotation_y(FRAC_PI_4),
+            Vec3::Y,
+        );
+        let obb = Obb3::from_bounding_box_conformal3(&bb, &m);
+        assert!((obb.volume() - 8.0).abs() < 1e-5);
+        for (corner, obb_corner) in bb.corners().into_iter().zip(obb.corners()) {
+            assert!(m.transform_point3(corner).abs_diff_eq(obb_corner, 1e-5));
+        }
+    }
+
+    #[test]
+    fn test_intersects() {
+        let unit = BoundingBox::from_center_size(Vec3::ZERO, Vec3::ONE);
+        let a = Obb3::from_bounding_box(&unit);
+
+        // A rotated box whose loose AABB overlaps `a`, but which doesn't itself overlap.
+        let b = Obb3::from_bounding_box_iso(
+            &unit,
+            &IsoTransform::from_rotation_translation(
+                Quat::from_rotation_z(FRAC_PI_4),
+                Vec3::new(1.1, 1.1, 0.0),
+            ),
+        );
+        assert!(
+            a.to_bounding_box()
+                .intersection(b.to_bounding_box())
+                .is_something()
+        );
+        assert!(!a.intersects_obb(&b));
+        assert!(!b.intersects_obb(&a));
+        assert!(!b.intersects_bounding_box(&unit));
+
+        // Move it closer so that its corner pokes into `a`.
+        let c = Obb3 {
+            center: Vec3::new(0.8, 0.8, 0.0),
+            ..b
+        };
+        assert!(a.intersects_obb(&c));
+        assert!(c.intersects_obb(&a));
+        assert!(c.intersects_bounding_box(&unit));
+
+        // Rotated around a different axis and separated along X.
+        let d = Obb3::from_center_half_size_rotation(
+            Vec3::new(1.2, 1.2, 0.0),
+            Vec3::splat(0.5),
+            Quat::from_rotation_x(FRAC_PI_4),
+        );
+        assert!(!a.intersects_obb(&d));
+
+        assert!(!a.intersects_bounding_box(&BoundingBox::nothing()));
+    }
+
+    #[test]
+    fn test_from_points() {
+        #![allow(clippy::float_cmp)]
+        assert_eq!(Obb3::from_points(&[]), None);
+
+        // A long thin box, rotated.
+        let rotation = Quat::from_axis_angle(Vec3::new(1.0, 2.0, 3.0).normalize(), 0.7);
+        let translation = Vec3::new(3.0, -2.0, 1.0);
+        let bb = BoundingBox::from_center_size(Vec3::ZERO, Vec3::new(8.0, 2.0, 0.5));
+        let expected = Obb3::from_bounding_box_iso(
+            &bb,
+            &IsoTransform::from_rotation_translation(rotation, translation),
+        );
+        let obb = Obb3::from_points(&expected.corners()).unwrap();
+
+        assert!(obb.center.abs_diff_eq(expected.center, 1e-4));
+        assert!((obb.volume() - expected.volume()).abs() < 1e-3);
+        for corner in expected.corners() {
+            assert!(obb.expanded_contains(corner, 1e-4));
+        }
+
+        // A single point
+        let obb = Obb3::from_points(&[Vec3::ONE]).unwrap();
+        assert_eq!(obb.center, Vec3::ONE);
+        assert_eq!(obb.half_size, Vec3::ZERO);
+    }
+
+    impl Obb3 {
+        fn expanded_contains(&self, point: Vec3, padding: f32) -> bool {
+            Self {
+                half_size: self.half_size + padding,
+                ..*self
+            }
+            .contains(point)
+        }
+    }
+}
@@ -37,6 +37,11 @@ impl Plane3 {
         d: 0.0,
     };
 
+    /// Planes whose normals are closer to parallel than this, measured as the sine of the angle
+    /// between them, are treated as parallel by [`Self::intersect_plane`] and
+    /// [`Self::intersect_three_planes`].
+    pub const PARALLEL_EPSILON: f32 = 1e-6;
+
     /// From the plane normal and a distance `d` so that for all points on the plane:
     /// `normal.dot(point) + d = 0`.
     #[inline]
@@ -53,6 +58,17 @@ impl Plane3 {
         }
     }
 
+    /// From three points on the plane.
+    ///
+    /// The normal is normalized, and faces the side from which `a`, `b` and `c`
+    /// are in counter-clockwise order.
+    /// If the points are on a line, the normal is zero.
+    #[inline]
+    pub fn from_points(a: Vec3, b: Vec3, c: Vec3) -> Self {
+        let normal = (b - a).cross(c - a).normalize_or_zero();
+        Self::from_normal_point(normal, a)
+    }
+
     /// Get normalized plane
     #[inline]
     #[must_use]
@@ -71,6 +87,144 @@ impl Plane3 {
         self.normal.dot(p) + self.d
     }
 
+    /// Returns the point on the plane closest to `p`.
+    #[inline]
+    pub fn project_point(&self, p: Vec3) -> Vec3 {
+        p - self.normal * (self.distance(p) / self.normal.length_squared())
+    }
+
+    /// The same plane, but facing the other way.
+    #[inline]
+    #[must_use]
+    pub fn flipped(&self) -> Self {
+        Self {
+            normal: -self.normal,
+            d: -self.d,
+        }
+    }
+
+    /// Transform the plane so that it contains the transformed points of the original plane.
+    ///
+    /// A normalized plane stays normalized.
+    #[inline]
+    #[must_use]
+    pub fn transform_iso(&self, m: &crate::IsoTransform) -> Self {
+        let normal = m.transform_vector3(self.normal);
+        Self::from_normal_dist(normal, self.d - normal.dot(m.translation()))
+    }
+
+    /// Transform the plane so that it contains the transformed points of the original plane.
+    ///
+    /// A normalized plane stays normalized.
+    #[inline]
+    #[must_use]
+    pub fn transform_conformal3(&self, m: &crate::Conformal3) -> Self {
+        let normal = m.rotation() * self.normal;
+        Self::from_normal_dist(normal, m.scale() * self.d - normal.dot(m.translation()))
+    }
+
+    /// Transform the plane so that it contains the transformed points of the original plane.
+    ///
+    /// Uses the inverse-transpose of the transform, so non-uniform scaling is handled correctly.
+    /// The result is not normalized.
+    #[must_use]
+    pub fn transform_affine3(&self, m: &crate::Affine3A) -> Self {
+        let normal = m.matrix3.inverse().transpose() * self.normal;
+        Self::from_normal_dist(normal, self.d - normal.dot(m.translation.into()))
+    }
+
+    /// Transform the plane so that it contains the transformed points of the original plane.
+    ///
+    /// Uses the inverse-transpose of the matrix, so this also works for projections.
+    /// The result is not normalized.
+    #[must_use]
+    pub fn transform_mat4(&self, m: &crate::Mat4) -> Self {
+        let v = m.inverse().transpose() * self.as_vec4();
+        Self::from_normal_dist(v.truncate(), v.w)
+    }
+
+    /// Split a convex polygon by the plane using Sutherland–Hodgman clipping.
+    ///
+    /// Returns the pieces in front of the plane (the side the normal points towards)
+    /// and behind it, with the winding order of the original polygon.
+    /// A piece is empty if the polygon has no area on that side.
+    /// A polygon lying in the plane is returned as the front piece.
+    #[cfg(not(target_arch = "spirv"))]
+    pub fn split_convex_polygon(&self, polygon: &[Vec3]) -> (Vec<Vec3>, Vec<Vec3>) {
+        self.split_convex_polygon_with(polygon, |p| *p, |a, b, t| a.lerp(*b, t))
+    }
+
+    /// Like [`Self::split_convex_polygon`], but for vertices with attributes.
+    ///
+    /// `position` returns the position of a vertex, and `lerp(a, b, t)` creates a new vertex
+    /// a fraction `t` of the way from `a` to `b` where an edge crosses the plane.
+    #[cfg(not(target_arch = "spirv"))]
+    pub fn split_convex_polygon_with<V: Clone>(
+        &self,
+        polygon: &[V],
+        position: impl Fn(&V) -> Vec3,
+        mut lerp: impl FnMut(&V, &V, f32) -> V,
+    ) -> (Vec<V>, Vec<V>) {
+        let dist: Vec<f32> = polygon.iter().map(|v| self.distance(position(v))).collect();
+        if dist.iter().all(|&d| d == 0.0) {
+            return (polygon.to_vec(), vec![]);
+        }
+
+        let mut front = vec![];
+        let mut back = vec![];
+        for (i, v) in polygon.iter().enumerate() {
+            let j = (i + 1) % polygon.len();
+            let (d, next_d) = (dist[i], dist[j]);
+            if 0.0 <= d {
+                front.push(v.clone());
+            }
+            if d <= 0.0 {
+                back.push(v.clone());
+            }
+            if (d < 0.0 && 0.0 < next_d) || (0.0 < d && next_d < 0.0) {
+                let crossing = lerp(v, &polygon[j], d / (d - next_d));
+                front.push(crossing.clone());
+                back.push(crossing);
+            }
+        }
+
+        // Only touching the plane from one side.
+        if front.len() < 3 {
+            front.clear();
+        }
+        if back.len() < 3 {
+            back.clear();
+        }
+        (front, back)
+    }
+
+    /// Split a triangle by the plane, interpolating vertex attributes where edges cross it.
+    ///
+    /// Returns the triangles in front of and behind the plane,
+    /// with the winding order of the original triangle.
+    /// See [`Self::split_convex_polygon_with`].
+    #[cfg(not(target_arch = "spirv"))]
+    pub fn split_triangle_with<V: Clone>(
+        &self,
+        triangle: [V; 3],
+        position: impl Fn(&V) -> Vec3,
+        lerp: impl FnMut(&V, &V, f32) -> V,
+    ) -> (Vec<[V; 3]>, Vec<[V; 3]>) {
+        let (front, back) = self.split_convex_polygon_with(&triangle, position, lerp);
+        let fan = |polygon: Vec<V>| -> Vec<[V; 3]> {
+            (2..polygon.len())
+                .map(|i| {
+                    [
+                        polygon[0].clone(),
+                        polygon[i - 1].clone(),
+                        polygon[i].clone(),
+                    ]
+                })
+                .collect()
+        };
+        (fan(front), fan(back))
+    }
+
     /// The bool is whether the plane was hit or not.
     ///
     /// If false, the ray was either perpendicular to the plane, or the ray shot away from the plane.
@@ -87,6 +241,49 @@ impl Plane3 {
         }
     }
 
+    /// The line where two planes intersect.
+    ///
+    /// The ray starts at the point on the line closest to the origin,
+    /// and has the normalized direction `self.normal.cross(other.normal)`.
+    ///
+    /// Returns [`None`] if the planes are parallel, or so close to parallel that the
+    /// sine of the angle between their normals is below [`Self::PARALLEL_EPSILON`].
+    #[cfg(not(target_arch = "spirv"))] // TODO: large Options in rust-gpu
+    pub fn intersect_plane(&self, other: &Self) -> Option<crate::Ray3> {
+        let (n1, n2) = (self.normal, other.normal);
+        let dir = n1.cross(n2);
+        let dir_length_squared = dir.length_squared();
+        let limit = Self::PARALLEL_EPSILON * n1.length() * n2.length();
+        if dir_length_squared <= limit * limit {
+            return None;
+        }
+
+        let origin = (-self.d * n2.cross(dir) - other.d * dir.cross(n1)) / dir_length_squared;
+        Some(crate::Ray3::from_origin_dir(
+            origin,
+            dir / dir_length_squared.sqrt(),
+        ))
+    }
+
+    /// The point where three planes intersect.
+    ///
+    /// Returns [`None`] if any two of the planes are parallel, or if all three share a line.
+    /// Inputs so close to this that the result would be meaningless are also rejected,
+    /// see [`Self::PARALLEL_EPSILON`].
+    #[cfg(not(target_arch = "spirv"))] // TODO: large Options in rust-gpu
+    pub fn intersect_three_planes(a: &Self, b: &Self, c: &Self) -> Option<Vec3> {
+        let bc = b.normal.cross(c.normal);
+        let ca = c.normal.cross(a.normal);
+        let ab = a.normal.cross(b.normal);
+        let det = a.normal.dot(bc);
+        let limit =
+            Self::PARALLEL_EPSILON * a.normal.length() * b.normal.length() * c.normal.length();
+        if det.abs() <= limit {
+            return None;
+        }
+        Some(-(a.d * bc + b.d * ca + c.d * ab) / det)
+    }
+
     /// True if every value is finite
     #[inline]
     pub fn is_finite(&self) -> bool {
@@ -113,4 +310,189 @@ mod test {
         let p = p.normalized();
         assert_eq!(p.distance(point), 0.0);
     }
+
+    #[test]
+    fn test_from_points_project_flip() {
+        #![allow(clippy::float_cmp)]
+        let p = Plane3::from_points(
+            Vec3::new(0.0, 0.0, 2.0),
+            Vec3::new(1.0, 0.0, 2.0),
+            Vec3::new(0.0, 1.0, 2.0),
+        );
+        assert_eq!(p, Plane3::from_normal_dist(Vec3::Z, -2.0));
+        assert_eq!(
+            p.project_point(Vec3::new(3.0, 4.0, 5.0)),
+            Vec3::new(3.0, 4.0, 2.0)
+        );
+
+        // Unnormalized planes project correctly too.
+        let unnormalized = Plane3::from_normal_dist(2.0 * Vec3::Z, -4.0);
+        assert_eq!(
+            unnormalized.project_point(Vec3::new(3.0, 4.0, 5.0)),
+            Vec3::new(3.0, 4.0, 2.0)
+        );
+
+        let flipped = p.flipped();
+        assert_eq!(flipped.normal, -Vec3::Z);
+        assert_eq!(flipped.distance(Vec3::ZERO), -p.distance(Vec3::ZERO));
+
+        let degenerate = Plane3::from_points(Vec3::ZERO, Vec3::X, 2.0 * Vec3::X);
+        assert_eq!(degenerate.normal, Vec3::ZERO);
+    }
+
+    #[test]
+    fn test_intersect_planes() {
+        let a = Plane3::from_normal_point(Vec3::X, Vec3::new(1.0, 0.0, 0.0));
+        let b = Plane3::from_normal_point(2.0 * Vec3::Y, Vec3::new(0.0, 2.0, 0.0));
+        let c = Plane3::from_normal_point(Vec3::new(0.0, 1.0, 1.0), Vec3::new(0.0, 0.0, 3.0));
+
+        let line = a.intersect_plane(&b).unwrap();
+        assert!(line.origin.abs_diff_eq(Vec3::new(1.0, 2.0, 0.0), 1e-6));
+        assert!(line.dir.abs_diff_eq(Vec3::Z, 1e-6));
+
+        let line = a.intersect_plane(&c).unwrap();
+        for t in [-3.0, 0.0, 5.0] {
+            let p = line.point_along(t);
+            assert!(a.distance(p).abs() < 1e-5);
+            assert!(c.distance(p).abs() < 1e-5);
+        }
+        assert!((line.dir.length() - 1.0).abs() < 1e-6);
+
+        let p = Plane3::intersect_three_planes(&a, &b, &c).unwrap();
+        assert!(p.abs_diff_eq(Vec3::new(1.0, 2.0, 1.0), 1e-6));
+
+        // Parallel and nearly parallel.
+        let parallel = Plane3::from_normal_dist(Vec3::X, 5.0);
+        assert_eq!(a.intersect_plane(&parallel), None);
+        assert_eq!(a.intersect_plane(&a.flipped()), None);
+        let nearly = Plane3::from_normal_dist(Vec3::new(1.0, 1e-8, 0.0), 5.0);
+        assert_eq!(a.intersect_plane(&nearly), None);
+        assert_eq!(Plane3::intersect_three_planes(&a, &b, &parallel), None);
+
+        // Three planes sharing a line.
+        let d = Plane3::from_normal_point(Vec3::new(1.0, 1.0, 0.0), Vec3::new(1.0, 2.0, 0.0));
+        assert_eq!(Plane3::intersect_three_planes(&a, &b, &d), None);
+    }
+
+    #[test]
+    fn test_split_polygon() {
+        let square = [
+            Vec3::new(0.0, 0.0, 0.0),
+            Vec3::new(2.0, 0.0, 0.0),
+            Vec3::new(2.0, 2.0, 0.0),
+            Vec3::new(0.0, 2.0, 0.0),
+        ];
+
+        let plane = Plane3::from_normal_point(Vec3::X, Vec3::new(0.5, 0.0, 0.0));
+        let (front, back) = plane.split_convex_polygon(&square);
+        assert_eq!(
+            front,
+            vec![
+                Vec3::new(0.5, 0.0, 0.0),
+                Vec3::new(2.0, 0.0, 0.0),
+                Vec3::new(2.0, 2.0, 0.0),
+                Vec3::new(0.5, 2.0, 0.0),
+            ]
+        );
+        assert_eq!(
+            back,
+            vec![
+                Vec3::new(0.0, 0.0, 0.0),
+                Vec3::new(0.5, 0.0, 0.0),
+                Vec3::new(0.5, 2.0, 0.0),
+                Vec3::new(0.0, 2.0, 0.0),
+            ]
+        );
+
+        // Through two corners.
+        let plane = Plane3::from_points(Vec3::ZERO, Vec3::Z, Vec3::new(2.0, 2.0, 0.0));
+        let (front, back) = plane.split_convex_polygon(&square);
+        assert_eq!(front.len(), 3);
+        assert_eq!(back.len(), 3);
+
+        // Touching an edge, or entirely on one side.
+        let plane = Plane3::from_normal_point(Vec3::X, Vec3::ZERO);
+        assert_eq!(
+            plane.split_convex_polygon(&square),
+            (square.to_vec(), vec![])
+        );
+        assert_eq!(
+            plane.flipped().split_convex_polygon(&square),
+            (vec![], square.to_vec())
+        );
+        assert_eq!(
+            Plane3::XY.split_convex_polygon(&square),
+            (square.to_vec(), vec![])
+        );
+    }
+
+    #[test]
+    fn test_split_triangle_with() {
+        // Vertices with a position and a scalar attribute.
+        let triangle = [
+            (Vec3::new(-1.0, 0.0, 0.0), 0.0),
+            (Vec3::new(1.0, 0.0, 0.0), 2.0),
+            (Vec3::new(1.0, 1.0, 0.0), 4.0),
+        ];
+        let (front, back) = Plane3::YZ.split_triangle_with(
+            triangle,
+            |v| v.0,
+            |a, b, t| (a.0.lerp(b.0, t), crate::lerp(a.1..=b.1, t)),
+        );
+        assert_eq!(front.len(), 2);
+        assert_eq!(back.len(), 1);
+        assert_eq!(back[0][0], triangle[0]);
+        assert_eq!(back[0][1], (Vec3::ZERO, 1.0));
+        assert_eq!(back[0][2], (Vec3::new(0.0, 0.5, 0.0), 2.0));
+    }
+
+    #[test]
+    fn test_transforms() {
+        use crate::{Affine3A, Conformal3, IsoTransform, Mat4, Quat};
+
+        let points = [
+            Vec3::new(1.0, 0.0, 0.0),
+            Vec3::new(0.0, 2.0, 0.0),
+            Vec3::new(0.0, 0.0, 3.0),
+        ];
+        let plane = Plane3::from_points(points[0], points[1], points[2]);
+        let off_plane = Vec3::splat(5.0);
+
+        let rotation = Quat::from_euler(glam::EulerRot::XYZ, 0.3, -1.2, 2.0);
+        let translation = Vec3::new(4.0, -2.0, 7.0);
+
+        let check = |transformed: Plane3, transform: &dyn Fn(Vec3) -> Vec3| {
+            let transformed = transformed.normalized();
+            for p in points {
+                assert!(transformed.distance(transform(p)).abs() < 1e-4);
+            }
+            // Stays on the same side.
+            assert!(transformed.distance(transform(off_plane)) > 0.0);
+        };
+
+        let iso = IsoTransform::from_rotation_translation(rotation, translation);
+        check(plane.transform_iso(&iso), &|p| iso.transform_point3(p));
+        assert!((plane.transform_iso(&iso).normal.length() - 1.0).abs() < 1e-6);
+
+        let conformal = Conformal3::from_scale_rotation_translation(3.0, rotation, translation);
+        let transformed = plane.transform_conformal3(&conformal);
+        check(transformed, &|p| conformal.transform_point3(p));
+        // Distances scale with the transform.
+        let d = plane.distance(off_plane);
+        assert!(
+            (transformed.distance(conformal.transform_point3(off_plane)) - 3.0 * d).abs() < 1e-4
+        );
+
+        let affine = Affine3A::from_scale_rotation_translation(
+            Vec3::new(1.0, 5.0, 0.5),
+            rotation,
+            translation,
+        );
+        check(plane.transform_affine3(&affine), &|p| {
+            affine.transform_point3(p)
+        });
+
+        let mat4 = Mat4::from(affine);
+        check(plane.transform_mat4(&mat4), &|p| mat4.transform_point3(p));
+    }
 }
@@ -0,0 +1,206 @@
+use crate::Line2;
+use crate::Side;
+use crate::Vec2;
+
+#[cfg(target_arch = "spirv")]
+use num_traits::Float;
+
+/// A ray in 2-dimensional space: a line through the plane with a starting point and a direction.
+///
+/// Any point on the ray can be found through the formula `origin + t * dir`,
+/// where t is a non-negative floating point value, which represents the distance
+/// along the ray.
+#[derive(Clone, Copy, Default, PartialEq)]
+#[cfg_attr(not(target_arch = "spirv"), derive(Debug))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "speedy", derive(speedy::Writable, speedy::Readable))]
+pub struct Ray2 {
+    /// Start of the ray
+    pub origin: Vec2,
+    /// Direction of the ray, normalized
+    pub dir: Vec2,
+}
+
+impl Ray2 {
+    /// An invalid ray, starting at the origin and going nowhere.
+    pub const ZERO: Self = Self {
+        origin: Vec2::ZERO,
+        dir: Vec2::ZERO,
+    };
+
+    /// `dir` should be normalized
+    #[inline]
+    pub fn from_origin_dir(origin: Vec2, dir: Vec2) -> Self {
+        Self { origin, dir }
+    }
+
+    /// Get normalized ray (where `dir.len() == 1`).
+    #[inline]
+    #[must_use]
+    pub fn normalize(&self) -> Self {
+        Self {
+            origin: self.origin,
+            dir: self.dir.normalize(),
+        }
+    }
+
+    /// True if every value is finite
+    #[inline]
+    pub fn is_finite(&self) -> bool {
+        self.origin.is_finite() && self.dir.is_finite()
+    }
+
+    #[inline]
+    pub fn point_along(&self, t: f32) -> Vec2 {
+        self.origin + t * self.dir
+    }
+
+    // Returns the distance along the ray that is closest to the given point.
+    // The returned `t` can be negative.
+    #[inline]
+    pub fn closest_t_to_point(&self, point: Vec2) -> f32 {
+        self.dir.dot(point - self.origin)
+    }
+
+    /// Returns the point along the ray that is closest to the given point.
+    /// The returned point may be "behind" the ray origin.
+    #[inline]
+    pub fn closest_point_to_point(&self, point: Vec2) -> Vec2 {
+        self.origin + self.dir * self.dir.dot(point - self.origin)
+    }
+
+    /// The infinite line the ray lies on, with its normal pointing to the left of the ray.
+    #[inline]
+    pub fn to_line2(&self) -> Line2 {
+        Line2::from_points(self.origin, self.origin + self.dir)
+    }
+
+    /// Which side of the ray the point is on, where [`Side::Front`] is to the left
+    /// (counter-clockwise, with Y up).
+    ///
+    /// Points within `epsilon` of the line through the ray are [`Side::On`] it.
+    #[inline]
+    pub fn side(&self, point: Vec2, epsilon: f32) -> Side {
+        self.to_line2().side(point, epsilon)
+    }
+
+    /// Intersect the ray with a line.
+    ///
+    /// Returns the distance `t` along the ray to where it crosses the line,
+    /// or [`None`] if the line is behind the ray or parallel to it.
+    #[cfg(not(target_arch = "spirv"))] // TODO: large Options in rust-gpu
+    pub fn intersect_line(&self, line: &Line2) -> Option<f32> {
+        let denom = line.normal.dot(self.dir);
+        if denom.abs() <= Line2::PARALLEL_EPSILON * line.normal.length() * self.dir.length() {
+            return None;
+        }
+        let t = -line.distance(self.origin) / denom;
+        (t >= 0.0).then_some(t)
+    }
+
+    /// Intersect the ray with another ray.
+    ///
+    /// Returns the distance along `self` and along `other` to the crossing point,
+    /// or [`None`] if the rays miss or are parallel.
+    #[cfg(not(target_arch = "spirv"))] // TODO: large Options in rust-gpu
+    pub fn intersect_ray(&self, other: &Self) -> Option<(f32, f32)> {
+        let (t, s) = line_line_ts(self.origin, self.dir, other.origin, other.dir)?;
+        (t >= 0.0 && s >= 0.0).then_some((t, s))
+    }
+
+    /// Intersect the ray with a line segment.
+    ///
+    /// Returns the distance along the ray, and the `t` in `[0, 1]` along the segment,
+    /// of the crossing point, or [`None`] if the ray misses or is parallel to the segment.
+    #[cfg(not(target_arch = "spirv"))] // TODO: large Options in rust-gpu
+    pub fn intersect_segment(&self, segment: &crate::Segment2) -> Option<(f32, f32)> {
+        let (t, s) = line_line_ts(self.origin, self.dir, segment.a, segment.dir())?;
+        (t >= 0.0 && (0.0..=1.0).contains(&s)).then_some((t, s))
+    }
+
+    /// Intersect the ray with a circle.
+    ///
+    /// Returns the distance `t` along the ray to the first point where it crosses the edge of
+    /// the circle and the outward normal there, or [`None`] if it misses.
+    /// If the ray starts inside the circle, this is where it exits.
+    /// The ray direction does not need to be normalized.
+    #[cfg(not(target_arch = "spirv"))] // TODO: large Options in rust-gpu
+    pub fn intersect_circle(&self, circle: &crate::Circle) -> Option<(f32, Vec2)> {
+        let offset = self.origin - circle.center;
+        let a = self.dir.length_squared();
+        let b = self.dir.dot(offset);
+        let c = offset.length_squared() - circle.radius * circle.radius;
+        let discriminant = b * b - a * c;
+        if discriminant < 0.0 || a == 0.0 {
+            return None;
+        }
+        let sqrt_discriminant = discriminant.sqrt();
+        let t_near = (-b - sqrt_discriminant) / a;
+        let t_far = (-b + sqrt_discriminant) / a;
+        let t = if t_near >= 0.0 { t_near } else { t_far };
+        (t >= 0.0).then(|| (t, (self.point_along(t) - circle.center).normalize_or_zero()))
+    }
+}
+
+/// Where the lines `p + t * d` and `q + s * e` cross, as `(t, s)`.
+///
+/// Returns [`None`] if the lines are parallel, see [`Line2::PARALLEL_EPSILON`].
+#[cfg(not(target_arch = "spirv"))]
+pub(crate) fn line_line_ts(p: Vec2, d: Vec2, q: Vec2, e: Vec2) -> Option<(f32, f32)> {
+    let denom = d.perp_dot(e);
+    if denom.abs() <= Line2::PARALLEL_EPSILON * d.length() * e.length() {
+        return None;
+    }
+    let r = q - p;
+    Some((r.perp_dot(e) / denom, r.perp_dot(d) / denom))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::Circle;
+    use crate::Segment2;
+
+    #[test]
+    fn test_ray2() {
+        #![allow(clippy::float_cmp)]
+        let ray = Ray2::from_origin_dir(Vec2::new(-2.0, 1.0), Vec2::X);
+        assert_eq!(ray.point_along(3.0), Vec2::new(1.0, 1.0));
+        assert_eq!(ray.closest_t_to_point(Vec2::new(-5.0, 4.0)), -3.0);
+        assert_eq!(
+            ray.closest_point_to_point(Vec2::new(3.0, -1.0)),
+            Vec2::new(3.0, 1.0)
+        );
+        assert_eq!(ray.side(Vec2::new(0.0, 2.0), 1e-6), Side::Front);
+        assert_eq!(ray.side(Vec2::new(0.0, 0.0), 1e-6), Side::Back);
+        assert_eq!(ray.side(Vec2::new(-10.0, 1.0), 1e-6), Side::On);
+
+        assert_eq!(ray.intersect_line(&Line2::Y), Some(2.0));
+        assert_eq!(ray.intersect_line(&Line2::Y.flipped()), Some(2.0));
+        assert_eq!(ray.intersect_line(&Line2::X), None);
+        let behind = Line2::from_normal_point(Vec2::X, Vec2::new(-3.0, 0.0));
+        assert_eq!(ray.intersect_line(&behind), None);
+
+        let up = Ray2::from_origin_dir(Vec2::new(1.0, -1.0), Vec2::Y);
+        assert_eq!(ray.intersect_ray(&up), Some((3.0, 2.0)));
+        assert_eq!(up.intersect_ray(&ray), Some((2.0, 3.0)));
+        let down = Ray2::from_origin_dir(Vec2::new(1.0, -1.0), Vec2::NEG_Y);
+        assert_eq!(ray.intersect_ray(&down), None);
+        assert_eq!(ray.intersect_ray(&ray), None);
+
+        let segment = Segment2::new(Vec2::new(0.0, 0.0), Vec2::new(0.0, 4.0));
+        assert_eq!(ray.intersect_segment(&segment), Some((2.0, 0.25)));
+        let short = Segment2::new(Vec2::new(0.0, 2.0), Vec2::new(0.0, 4.0));
+        assert_eq!(ray.intersect_segment(&short), None);
+
+        let circle = Circle::from_center_radius(Vec2::new(2.0, 1.0), 1.0);
+        assert_eq!(ray.intersect_circle(&circle), Some((3.0, Vec2::NEG_X)));
+        let inside = Ray2::from_origin_dir(circle.center, Vec2::Y);
+        assert_eq!(inside.intersect_circle(&circle), Some((1.0, Vec2::Y)));
+        assert_eq!(
+            Ray2::from_origin_dir(Vec2::ZERO, Vec2::NEG_X).intersect_circle(&circle),
+            None
+        );
+        assert_eq!(Ray2::ZERO.intersect_circle(&circle), None);
+    }
+}
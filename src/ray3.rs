@@ -50,6 +50,16 @@ impl Ray3 {
         }
     }
 
+    /// The component-wise reciprocal of the direction, `1.0 / dir`.
+    ///
+    /// Components where `dir` is zero become infinite.
+    /// Precompute this when testing one ray against many bounding boxes,
+    /// see [`crate::BoundingBox::intersect_ray_inv_dir`].
+    #[inline]
+    pub fn inv_dir(&self) -> Vec3 {
+        self.dir.recip()
+    }
+
     /// True if every value is finite
     #[inline]
     pub fn is_finite(&self) -> bool {
@@ -115,6 +125,241 @@ impl Ray3 {
     pub fn closest_point_to_point(&self, point: Vec3) -> Vec3 {
         self.origin + self.dir * self.dir.dot(point - self.origin)
     }
+
+    /// Intersect the ray with a sphere.
+    ///
+    /// Returns the distance `t` along the ray to the first point where it crosses the surface
+    /// and the outward surface normal there, or [`None`] if it misses.
+    /// If the ray starts inside the sphere, this is where it exits.
+    /// The ray direction does not need to be normalized.
+    #[cfg(not(target_arch = "spirv"))] // TODO: large Options in rust-gpu
+    pub fn intersect_sphere(&self, center: Vec3, radius: f32) -> Option<(f32, Vec3)> {
+        let mut hit = NearestHit::default();
+        self.add_sphere_hits(&mut hit, center, radius, |_| true);
+        hit.0
+    }
+
+    /// Intersect the ray with a triangle using the Möller–Trumbore algorithm.
+    ///
+    /// Returns the distance `t` along the ray, the normal of the triangle, and the barycentric
+    /// coordinates of the hit, i.e. the weights of `a`, `b` and `c` that sum to one.
+    /// The normal faces the side where `a`, `b` and `c` are in counter-clockwise order.
+    ///
+    /// If `cull_backfaces` is set, hits from behind the triangle (against the normal) are ignored.
+    /// Degenerate triangles are never hit.
+    #[cfg(not(target_arch = "spirv"))] // TODO: large Options in rust-gpu
+    pub fn intersect_triangle(
+        &self,
+        [a, b, c]: [Vec3; 3],
+        cull_backfaces: bool,
+    ) -> Option<(f32, Vec3, Vec3)> {
+        let e1 = b - a;
+        let e2 = c - a;
+        let p = self.dir.cross(e2);
+        let det = e1.dot(p);
+        // A positive determinant means we hit the front side.
+        if det == 0.0 || (cull_backfaces && det < 0.0) {
+            return None;
+        }
+
+        let inv_det = det.recip();
+        let s = self.origin - a;
+        let u = s.dot(p) * inv_det;
+        if !(0.0..=1.0).contains(&u) {
+            return None;
+        }
+
+        let q = s.cross(e1);
+        let v = self.dir.dot(q) * inv_det;
+        if v < 0.0 || 1.0 < u + v {
+            return None;
+        }
+
+        let t = e2.dot(q) * inv_det;
+        (0.0 <= t).then(|| (t, e1.cross(e2).normalize(), Vec3::new(1.0 - u - v, u, v)))
+    }
+
+    /// Intersect the ray with a capsule: all points within `radius` of the line segment `a`-`b`.
+    ///
+    /// Returns the distance `t` along the ray to the first point where it crosses the surface
+    /// and the outward surface normal there, or [`None`] if it misses.
+    /// If the ray starts inside the capsule, this is where it exits.
+    /// The ray direction does not need to be normalized.
+    #[cfg(not(target_arch = "spirv"))] // TODO: large Options in rust-gpu
+    pub fn intersect_capsule(&self, a: Vec3, b: Vec3, radius: f32) -> Option<(f32, Vec3)> {
+        let length = a.distance(b);
+        if length == 0.0 {
+            return self.intersect_sphere(a, radius);
+        }
+        let axis = (b - a) / length;
+        let height = |p: Vec3| (p - a).dot(axis);
+
+        let mut hit = NearestHit::default();
+        self.add_cylinder_side_hits(&mut hit, a, axis, length, radius);
+        self.add_sphere_hits(&mut hit, a, radius, |p| height(p) <= 0.0);
+        self.add_sphere_hits(&mut hit, b, radius, |p| length <= height(p));
+        hit.0
+    }
+
+    /// Intersect the ray with a capped cylinder around the line segment `a`-`b`.
+    ///
+    /// Returns the distance `t` along the ray to the first point where it crosses the surface
+    /// and the outward surface normal there, or [`None`] if it misses.
+    /// If the ray starts inside the cylinder, this is where it exits.
+    /// The ray direction does not need to be normalized.
+    #[cfg(not(target_arch = "spirv"))] // TODO: large Options in rust-gpu
+    pub fn intersect_cylinder(&self, a: Vec3, b: Vec3, radius: f32) -> Option<(f32, Vec3)> {
+        let length = a.distance(b);
+        if length == 0.0 {
+            return None;
+        }
+        let axis = (b - a) / length;
+
+        let mut hit = NearestHit::default();
+        self.add_cylinder_side_hits(&mut hit, a, axis, length, radius);
+        hit.add(self.intersect_disk(a, -axis, radius));
+        hit.add(self.intersect_disk(b, axis, radius));
+        hit.0
+    }
+
+    /// Intersect the ray with a capped cone with its tip at `apex`,
+    /// and a base disk of the given `radius` at `base`.
+    ///
+    /// Returns the distance `t` along the ray to the first point where it crosses the surface
+    /// and the outward surface normal there, or [`None`] if it misses.
+    /// If the ray starts inside the cone, this is where it exits.
+    /// The ray direction does not need to be normalized.
+    #[cfg(not(target_arch = "spirv"))] // TODO: large Options in rust-gpu
+    pub fn intersect_cone(&self, apex: Vec3, base: Vec3, radius: f32) -> Option<(f32, Vec3)> {
+        let length = apex.distance(base);
+        if length == 0.0 {
+            return None;
+        }
+        let axis = (base - apex) / length;
+        let cos2 = length * length / (length * length + radius * radius);
+
+        // Points `p` on the infinite double cone satisfy `((p - apex)·axis)² = cos² |p - apex|²`.
+        let (d, co) = (self.dir, self.origin - apex);
+        let (dv, cv) = (d.dot(axis), co.dot(axis));
+        let qa = dv * dv - cos2 * d.length_squared();
+        let qb = dv * cv - cos2 * d.dot(co);
+        let qc = cv * cv - cos2 * co.length_squared();
+
+        let mut hit = NearestHit::default();
+        for t in solve_quadratic(qa, qb, qc).into_iter().flatten() {
+            let q = self.point_along(t) - apex;
+            let height = q.dot(axis);
+            if (0.0..=length).contains(&height) {
+                let normal = (cos2 * q - height * axis).normalize_or(-axis);
+                hit.add(Some((t, normal)));
+            }
+        }
+        hit.add(self.intersect_disk(base, axis, radius));
+        hit.0
+    }
+
+    /// Intersect the ray with a flat disk.
+    ///
+    /// Returns the distance `t` along the ray and the given `normal`, or [`None`] if it misses.
+    /// Both sides of the disk can be hit.
+    /// `normal` should be normalized, but the ray direction does not need to be.
+    #[cfg(not(target_arch = "spirv"))] // TODO: large Options in rust-gpu
+    pub fn intersect_disk(&self, center: Vec3, normal: Vec3, radius: f32) -> Option<(f32, Vec3)> {
+        let denom = self.dir.dot(normal);
+        if denom == 0.0 {
+            return None;
+        }
+        let t = (center - self.origin).dot(normal) / denom;
+        let inside = self.point_along(t).distance_squared(center) <= radius * radius;
+        (0.0 <= t && inside).then_some((t, normal))
+    }
+
+    /// Add the hits on the surface of a sphere where `valid` returns `true`.
+    #[cfg(not(target_arch = "spirv"))]
+    fn add_sphere_hits(
+        &self,
+        hit: &mut NearestHit,
+        center: Vec3,
+        radius: f32,
+        valid: impl Fn(Vec3) -> bool,
+    ) {
+        let oc = self.origin - center;
+        let roots = solve_quadratic(
+            self.dir.length_squared(),
+            oc.dot(self.dir),
+            oc.length_squared() - radius * radius,
+        );
+        for t in roots.into_iter().flatten() {
+            let p = self.point_along(t);
+            if valid(p) {
+                hit.add(Some((t, (p - center) / radius)));
+            }
+        }
+    }
+
+    /// Add the hits on the side of a cylinder from `a` to `a + length * axis`.
+    #[cfg(not(target_arch = "spirv"))]
+    fn add_cylinder_side_hits(
+        &self,
+        hit: &mut NearestHit,
+        a: Vec3,
+        axis: Vec3,
+        length: f32,
+        radius: f32,
+    ) {
+        // Project onto the plane perpendicular to the axis, where the side is a circle.
+        let oa = self.origin - a;
+        let d = self.dir - self.dir.dot(axis) * axis;
+        let o = oa - oa.dot(axis) * axis;
+        let roots = solve_quadratic(
+            d.length_squared(),
+            o.dot(d),
+            o.length_squared() - radius * radius,
+        );
+        for t in roots.into_iter().flatten() {
+            let p = self.point_along(t) - a;
+            let height = p.dot(axis);
+            if (0.0..=length).contains(&height) {
+                hit.add(Some((t, (p - height * axis) / radius)));
+            }
+        }
+    }
+}
+
+/// Keeps track of the nearest hit in front of the ray origin.
+#[cfg(not(target_arch = "spirv"))]
+#[derive(Default)]
+struct NearestHit(Option<(f32, Vec3)>);
+
+#[cfg(not(target_arch = "spirv"))]
+impl NearestHit {
+    fn add(&mut self, hit: Option<(f32, Vec3)>) {
+        let is_nearer =
+            |&(t, _): &(f32, Vec3)| 0.0 <= t && self.0.is_none_or(|(nearest_t, _)| t < nearest_t);
+        if let Some(hit) = hit.filter(is_nearer) {
+            self.0 = Some(hit);
+        }
+    }
+}
+
+/// Solve `a t² + 2 half_b t + c = 0`, returning the real roots.
+#[cfg(not(target_arch = "spirv"))]
+fn solve_quadratic(a: f32, half_b: f32, c: f32) -> [Option<f32>; 2] {
+    if a == 0.0 {
+        if half_b == 0.0 {
+            [None, None]
+        } else {
+            [Some(-c / (2.0 * half_b)), None]
+        }
+    } else {
+        let discriminant = half_b * half_b - a * c;
+        if discriminant < 0.0 {
+            [None, None]
+        } else {
+            let sqrt = discriminant.sqrt();
+            [Some((-half_b - sqrt) / a), Some((-half_b + sqrt) / a)]
+        }
+    }
 }
 
 impl core::ops::Mul<Ray3> for crate::IsoTransform {
@@ -179,3 +424,119 @@ impl std::fmt::Debug for Ray3 {
             .finish()
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn assert_hit(hit: Option<(f32, Vec3)>, t: f32, normal: Vec3) {
+        let (hit_t, hit_normal) = hit.unwrap();
+        assert!((hit_t - t).abs() < 1e-5, "{hit_t} != {t}");
+        assert!(
+            hit_normal.abs_diff_eq(normal, 1e-5),
+            "{hit_normal} != {normal}"
+        );
+    }
+
+    #[test]
+    fn test_intersect_sphere() {
+        let ray = Ray3::from_origin_dir(Vec3::new(-5.0, 0.0, 0.0), Vec3::X);
+        assert_hit(ray.intersect_sphere(Vec3::ZERO, 2.0), 3.0, -Vec3::X);
+        assert_hit(
+            ray.intersect_sphere(Vec3::new(-5.0, 1.0, 0.0), 2.0),
+            3.0_f32.sqrt(),
+            Vec3::new(3.0_f32.sqrt() / 2.0, -0.5, 0.0),
+        );
+        assert_eq!(ray.intersect_sphere(Vec3::new(0.0, 3.0, 0.0), 2.0), None);
+        assert_eq!(ray.intersect_sphere(Vec3::new(-10.0, 0.0, 0.0), 2.0), None);
+
+        // Unnormalized direction.
+        let ray = Ray3::from_origin_dir(Vec3::new(-5.0, 0.0, 0.0), 2.0 * Vec3::X);
+        assert_hit(ray.intersect_sphere(Vec3::ZERO, 2.0), 1.5, -Vec3::X);
+    }
+
+    #[test]
+    fn test_intersect_triangle() {
+        let triangle = [Vec3::ZERO, Vec3::X, Vec3::Y];
+        let ray = Ray3::from_origin_dir(Vec3::new(0.25, 0.5, 2.0), -Vec3::Z);
+        let (t, normal, barycentric) = ray.intersect_triangle(triangle, true).unwrap();
+        assert_eq!(t, 2.0);
+        assert_eq!(normal, Vec3::Z);
+        assert!(barycentric.abs_diff_eq(Vec3::new(0.25, 0.25, 0.5), 1e-6));
+
+        // From behind.
+        let ray = Ray3::from_origin_dir(Vec3::new(0.25, 0.5, -2.0), Vec3::Z);
+        assert_eq!(ray.intersect_triangle(triangle, true), None);
+        assert_eq!(ray.intersect_triangle(triangle, false).unwrap().0, 2.0);
+
+        // Outside, behind the ray, and parallel.
+        let ray = Ray3::from_origin_dir(Vec3::new(0.75, 0.5, 2.0), -Vec3::Z);
+        assert_eq!(ray.intersect_triangle(triangle, false), None);
+        let ray = Ray3::from_origin_dir(Vec3::new(0.25, 0.25, 2.0), Vec3::Z);
+        assert_eq!(ray.intersect_triangle(triangle, false), None);
+        let ray = Ray3::from_origin_dir(Vec3::new(-1.0, 0.25, 0.0), Vec3::X);
+        assert_eq!(ray.intersect_triangle(triangle, false), None);
+    }
+
+    #[test]
+    fn test_intersect_capsule_and_cylinder() {
+        let (a, b) = (Vec3::ZERO, Vec3::new(0.0, 4.0, 0.0));
+
+        // Hitting the side.
+        let ray = Ray3::from_origin_dir(Vec3::new(-5.0, 2.0, 0.0), Vec3::X);
+        assert_hit(ray.intersect_capsule(a, b, 1.0), 4.0, -Vec3::X);
+        assert_hit(ray.intersect_cylinder(a, b, 1.0), 4.0, -Vec3::X);
+
+        // Hitting the ends from above.
+        let ray = Ray3::from_origin_dir(Vec3::new(0.0, 10.0, 0.0), -Vec3::Y);
+        assert_hit(ray.intersect_capsule(a, b, 1.0), 5.0, Vec3::Y);
+        assert_hit(ray.intersect_cylinder(a, b, 1.0), 6.0, Vec3::Y);
+
+        // Past the cylinder cap, but within the capsule's hemisphere.
+        let ray = Ray3::from_origin_dir(Vec3::new(-5.0, 4.5, 0.0), Vec3::X);
+        let (t, normal) = ray.intersect_capsule(a, b, 1.0).unwrap();
+        assert!((t - (5.0 - 0.75_f32.sqrt())).abs() < 1e-5);
+        assert!(normal.y > 0.0 && normal.x < 0.0);
+        assert_eq!(ray.intersect_cylinder(a, b, 1.0), None);
+
+        // Starting inside, the exit.
+        let ray = Ray3::from_origin_dir(Vec3::new(0.0, 2.0, 0.0), Vec3::X);
+        assert_hit(ray.intersect_capsule(a, b, 1.0), 1.0, Vec3::X);
+        let ray = Ray3::from_origin_dir(Vec3::new(0.0, 2.0, 0.0), -Vec3::Y);
+        assert_hit(ray.intersect_capsule(a, b, 1.0), 3.0, -Vec3::Y);
+        assert_hit(ray.intersect_cylinder(a, b, 1.0), 2.0, -Vec3::Y);
+
+        // Parallel to the axis, outside.
+        let ray = Ray3::from_origin_dir(Vec3::new(2.0, -5.0, 0.0), Vec3::Y);
+        assert_eq!(ray.intersect_capsule(a, b, 1.0), None);
+        assert_eq!(ray.intersect_cylinder(a, b, 1.0), None);
+    }
+
+    #[test]
+    fn test_intersect_cone_and_disk() {
+        // A cone with a 45 degree half-angle, pointing up.
+        let (apex, base) = (Vec3::new(0.0, 2.0, 0.0), Vec3::ZERO);
+
+        let ray = Ray3::from_origin_dir(Vec3::new(-5.0, 1.0, 0.0), Vec3::X);
+        assert_hit(
+            ray.intersect_cone(apex, base, 2.0),
+            4.0,
+            Vec3::new(-1.0, 1.0, 0.0).normalize(),
+        );
+
+        let ray = Ray3::from_origin_dir(Vec3::new(0.5, -5.0, 0.0), Vec3::Y);
+        assert_hit(ray.intersect_cone(apex, base, 2.0), 5.0, -Vec3::Y);
+
+        // Above the apex, which is outside the capped cone.
+        let ray = Ray3::from_origin_dir(Vec3::new(-5.0, 3.0, 0.0), Vec3::X);
+        assert_eq!(ray.intersect_cone(apex, base, 2.0), None);
+
+        let ray = Ray3::from_origin_dir(Vec3::new(0.5, 3.0, 0.5), -Vec3::Y);
+        assert_hit(ray.intersect_disk(Vec3::ZERO, Vec3::Y, 1.0), 3.0, Vec3::Y);
+        assert_eq!(ray.intersect_disk(Vec3::ZERO, Vec3::Y, 0.5), None);
+        assert_eq!(
+            ray.intersect_disk(Vec3::new(0.0, 4.0, 0.0), Vec3::Y, 1.0),
+            None
+        );
+    }
+}
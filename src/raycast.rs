@@ -0,0 +1,172 @@
+use crate::BoundingBox;
+use crate::BoundingSphere;
+use crate::Obb3;
+use crate::Plane3;
+use crate::Ray3;
+use crate::Vec3;
+
+/// Where a ray hit a shape.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RayHit {
+    /// Distance along the ray, so that `point == ray.point_along(t)`.
+    pub t: f32,
+    /// The point on the surface of the shape.
+    pub point: Vec3,
+    /// The normalized surface normal at `point`.
+    pub normal: Vec3,
+}
+
+impl RayHit {
+    /// Create a hit at `t` along the ray.
+    #[inline]
+    pub fn from_ray_t_normal(ray: &Ray3, t: f32, normal: Vec3) -> Self {
+        Self {
+            t,
+            point: ray.point_along(t),
+            normal,
+        }
+    }
+}
+
+/// Shapes that can be hit by a [`Ray3`].
+pub trait Raycast {
+    /// Find the first point where the ray crosses the surface of the shape,
+    /// considering only hits in the range `[0, max_t]`.
+    ///
+    /// For solid shapes, the normal points outwards.
+    /// If the ray starts inside a solid shape, the hit is where the ray exits it.
+    fn raycast(&self, ray: &Ray3, max_t: f32) -> Option<RayHit>;
+}
+
+/// Hits are from either side of the plane, and the normal is the (normalized) plane normal.
+impl Raycast for Plane3 {
+    fn raycast(&self, ray: &Ray3, max_t: f32) -> Option<RayHit> {
+        let (hit, t) = self.intersect_ray(ray.origin, ray.dir);
+        (hit && t <= max_t).then(|| RayHit::from_ray_t_normal(ray, t, self.normal.normalize()))
+    }
+}
+
+impl Raycast for BoundingBox {
+    fn raycast(&self, ray: &Ray3, max_t: f32) -> Option<RayHit> {
+        let ((t_enter, enter_axis), (t_exit, exit_axis)) =
+            self.clip_ray_inv_dir(ray.origin, ray.inv_dir())?;
+
+        // We enter a face against the ray direction, and exit along it.
+        let (t, axis, sign) = if 0.0 <= t_enter {
+            (t_enter, enter_axis, -1.0)
+        } else {
+            (t_exit, exit_axis, 1.0)
+        };
+        // Never leaving an unbounded box is not a hit.
+        if max_t < t || !t.is_finite() {
+            return None;
+        }
+
+        let mut normal = Vec3::ZERO;
+        normal[axis] = sign * ray.dir[axis].signum();
+        Some(RayHit::from_ray_t_normal(ray, t, normal))
+    }
+}
+
+impl Raycast for BoundingSphere {
+    fn raycast(&self, ray: &Ray3, max_t: f32) -> Option<RayHit> {
+        if self.is_nothing() {
+            return None;
+        }
+        let (t, normal) = ray.intersect_sphere(self.center, self.radius)?;
+        (t <= max_t).then(|| RayHit::from_ray_t_normal(ray, t, normal))
+    }
+}
+
+impl Raycast for Obb3 {
+    fn raycast(&self, ray: &Ray3, max_t: f32) -> Option<RayHit> {
+        // Raycast the box in its local space, where it is axis aligned.
+        let inv_rotation = self.rotation.inverse();
+        let local_ray = Ray3::from_origin_dir(
+            inv_rotation * (ray.origin - self.center),
+            inv_rotation * ray.dir,
+        );
+        let local_box = BoundingBox::from_min_max(-self.half_size, self.half_size);
+        let hit = local_box.raycast(&local_ray, max_t)?;
+        Some(RayHit::from_ray_t_normal(
+            ray,
+            hit.t,
+            self.rotation * hit.normal,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::Quat;
+
+    #[test]
+    fn test_raycast_shapes() {
+        #![allow(clippy::float_cmp)]
+        let ray = Ray3::from_origin_dir(Vec3::new(-5.0, 0.5, 0.5), Vec3::X);
+
+        let plane = Plane3::from_normal_point(Vec3::X, Vec3::ZERO);
+        let hit = plane.raycast(&ray, 10.0).unwrap();
+        assert_eq!(hit.t, 5.0);
+        assert_eq!(hit.point, Vec3::new(0.0, 0.5, 0.5));
+        assert_eq!(hit.normal, Vec3::X);
+        assert_eq!(plane.raycast(&ray, 4.0), None);
+
+        let bb = BoundingBox::from_min_max(Vec3::ZERO, Vec3::ONE);
+        let hit = bb.raycast(&ray, 10.0).unwrap();
+        assert_eq!(hit.t, 5.0);
+        assert_eq!(hit.normal, -Vec3::X);
+        assert_eq!(bb.raycast(&ray, 4.0), None);
+
+        // From inside, we hit the exit.
+        let inside = Ray3::from_origin_dir(Vec3::splat(0.5), -Vec3::Y);
+        let hit = bb.raycast(&inside, 10.0).unwrap();
+        assert_eq!(hit.t, 0.5);
+        assert_eq!(hit.point, Vec3::new(0.5, 0.0, 0.5));
+        assert_eq!(hit.normal, -Vec3::Y);
+
+        // Never leaving an unbounded box.
+        assert_eq!(BoundingBox::everything().raycast(&ray, f32::INFINITY), None);
+        let column = BoundingBox::from_min_max(Vec3::ZERO, Vec3::new(1.0, f32::INFINITY, 1.0));
+        let up = Ray3::from_origin_dir(Vec3::splat(0.5), Vec3::Y);
+        assert_eq!(column.raycast(&up, f32::INFINITY), None);
+        assert_eq!(column.raycast(&inside, 10.0).unwrap().t, 0.5);
+
+        let sphere = BoundingSphere::from_center_radius(Vec3::new(0.0, 0.5, 0.5), 2.0);
+        let hit = sphere.raycast(&ray, 10.0).unwrap();
+        assert_eq!(hit.t, 3.0);
+        assert_eq!(hit.normal, -Vec3::X);
+        assert_eq!(BoundingSphere::nothing().raycast(&ray, 10.0), None);
+
+        // A box rotated 90 degrees around Z, so that its long side is along Y.
+        let obb = Obb3::from_center_half_size_rotation(
+            Vec3::ZERO,
+            Vec3::new(3.0, 1.0, 1.0),
+            Quat::from_rotation_z(std::f32::consts::FRAC_PI_2),
+        );
+        let hit = obb.raycast(&ray, 10.0).unwrap();
+        assert!((hit.t - 4.0).abs() < 1e-5);
+        assert!(hit.normal.abs_diff_eq(-Vec3::X, 1e-5));
+        let down = Ray3::from_origin_dir(Vec3::new(0.0, 5.0, 0.0), -Vec3::Y);
+        let hit = obb.raycast(&down, 10.0).unwrap();
+        assert!((hit.t - 2.0).abs() < 1e-5);
+        assert!(hit.normal.abs_diff_eq(Vec3::Y, 1e-5));
+    }
+
+    #[test]
+    fn test_raycast_mixed() {
+        let shapes: [&dyn Raycast; 3] = [
+            &BoundingBox::from_center_size(Vec3::new(8.0, 0.0, 0.0), Vec3::ONE),
+            &BoundingSphere::from_center_radius(Vec3::new(4.0, 0.0, 0.0), 1.0),
+            &Plane3::from_normal_point(Vec3::X, Vec3::new(6.0, 0.0, 0.0)),
+        ];
+        let ray = Ray3::from_origin_dir(Vec3::ZERO, Vec3::X);
+        let nearest = shapes
+            .iter()
+            .enumerate()
+            .filter_map(|(i, shape)| Some((i, shape.raycast(&ray, f32::INFINITY)?)))
+            .min_by(|a, b| a.1.t.total_cmp(&b.1.t));
+        assert_eq!(nearest.map(|(i, hit)| (i, hit.t)), Some((1, 3.0)));
+    }
+}
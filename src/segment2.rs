@@ -0,0 +1,305 @@
+use crate::Line2;
+use crate::Ray2;
+use crate::Side;
+use crate::Vec2;
+
+/// A line segment in 2-dimensional space between the points `a` and `b`.
+///
+/// Any point on the segment can be found through the formula `a + t * (b - a)`,
+/// where `t` is in the range `[0, 1]`.
+#[derive(Clone, Copy, Default, PartialEq)]
+#[cfg_attr(not(target_arch = "spirv"), derive(Debug))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "speedy", derive(speedy::Writable, speedy::Readable))]
+pub struct Segment2 {
+    /// Start of the segment.
+    pub a: Vec2,
+    /// End of the segment.
+    pub b: Vec2,
+}
+
+impl Segment2 {
+    /// Create a segment between two points.
+    #[inline]
+    pub fn new(a: Vec2, b: Vec2) -> Self {
+        Self { a, b }
+    }
+
+    /// The vector from `a` to `b`.
+    #[inline]
+    pub fn dir(&self) -> Vec2 {
+        self.b - self.a
+    }
+
+    /// The distance between `a` and `b`.
+    #[inline]
+    pub fn length(&self) -> f32 {
+        self.a.distance(self.b)
+    }
+
+    /// The squared distance between `a` and `b`.
+    #[inline]
+    pub fn length_squared(&self) -> f32 {
+        self.a.distance_squared(self.b)
+    }
+
+    /// The point halfway between `a` and `b`.
+    #[inline]
+    pub fn midpoint(&self) -> Vec2 {
+        self.a.midpoint(self.b)
+    }
+
+    /// True if `a == b`.
+    #[inline]
+    pub fn is_point(&self) -> bool {
+        self.a == self.b
+    }
+
+    /// True if every value is finite
+    #[inline]
+    pub fn is_finite(&self) -> bool {
+        self.a.is_finite() && self.b.is_finite()
+    }
+
+    /// The point `a + t * (b - a)`.
+    #[inline]
+    pub fn point_along(&self, t: f32) -> Vec2 {
+        self.a.lerp(self.b, t)
+    }
+
+    /// A ray starting at `a` going towards `b`, with an unnormalized direction,
+    /// so that `t` along the ray and `t` along the segment are the same.
+    #[inline]
+    pub fn to_ray2(&self) -> Ray2 {
+        Ray2::from_origin_dir(self.a, self.dir())
+    }
+
+    /// The infinite line through the segment, with its normal pointing to the left
+    /// when going from `a` to `b`.
+    #[inline]
+    pub fn to_line2(&self) -> Line2 {
+        Line2::from_points(self.a, self.b)
+    }
+
+    /// Which side of the segment the point is on, where [`Side::Front`] is to the left when
+    /// going from `a` to `b` (counter-clockwise, with Y up).
+    ///
+    /// Points within `epsilon` of the line through the segment are [`Side::On`] it.
+    #[inline]
+    pub fn side(&self, point: Vec2, epsilon: f32) -> Side {
+        self.to_line2().side(point, epsilon)
+    }
+
+    /// Returns the `t` in `[0, 1]` of the point on the segment closest to `point`.
+    ///
+    /// Returns 0 if the segment is a point.
+    pub fn closest_t_to_point(&self, point: Vec2) -> f32 {
+        let dir = self.dir();
+        let length_squared = dir.length_squared();
+        if length_squared == 0.0 {
+            0.0
+        } else {
+            (dir.dot(point - self.a) / length_squared).clamp(0.0, 1.0)
+        }
+    }
+
+    /// Returns the point on the segment closest to `point`.
+    #[inline]
+    pub fn closest_point_to_point(&self, point: Vec2) -> Vec2 {
+        self.point_along(self.closest_t_to_point(point))
+    }
+
+    /// The distance between the segment and `point`.
+    #[inline]
+    pub fn distance_to_point(&self, point: Vec2) -> f32 {
+        self.closest_point_to_point(point).distance(point)
+    }
+
+    /// Returns the `t` along both segments of the points where they are closest to each other.
+    ///
+    /// If the segments cross, this is where they cross.
+    /// If there are many such pairs of points, one of them is returned.
+    /// Segments that are points are handled as well.
+    pub fn closest_ts(&self, other: &Self) -> (f32, f32) {
+        let d1 = self.dir();
+        let d2 = other.dir();
+        let denom = d1.perp_dot(d2);
+        if denom != 0.0 {
+            let r = other.a - self.a;
+            let t = r.perp_dot(d2) / denom;
+            let s = r.perp_dot(d1) / denom;
+            if (0.0..=1.0).contains(&t) && (0.0..=1.0).contains(&s) {
+                return (t, s);
+            }
+        }
+
+        // In 2D, segments that don't cross are closest at an endpoint of one of them.
+        let candidates = [
+            (self.closest_t_to_point(other.a), 0.0),
+            (self.closest_t_to_point(other.b), 1.0),
+            (0.0, other.closest_t_to_point(self.a)),
+            (1.0, other.closest_t_to_point(self.b)),
+        ];
+        let mut best = candidates[0];
+        let mut best_distance_squared = f32::INFINITY;
+        for (t, s) in candidates {
+            let distance_squared = self.point_along(t).distance_squared(other.point_along(s));
+            if distance_squared < best_distance_squared {
+                best = (t, s);
+                best_distance_squared = distance_squared;
+            }
+        }
+        best
+    }
+
+    /// Returns the points on both segments where they are closest to each other.
+    #[inline]
+    pub fn closest_points(&self, other: &Self) -> (Vec2, Vec2) {
+        let (t, s) = self.closest_ts(other);
+        (self.point_along(t), other.point_along(s))
+    }
+
+    /// The distance between the closest points of the two segments.
+    #[inline]
+    pub fn distance_to_segment(&self, other: &Self) -> f32 {
+        let (p, q) = self.closest_points(other);
+        p.distance(q)
+    }
+
+    /// Intersect the segment with another segment.
+    ///
+    /// Returns the `t` along `self` and along `other` of the crossing point,
+    /// or [`None`] if they don't cross.
+    /// If the segments are collinear and overlap, this is the point of the overlap closest to `self.a`.
+    #[cfg(not(target_arch = "spirv"))] // TODO: large Options in rust-gpu
+    pub fn intersect_segment(&self, other: &Self) -> Option<(f32, f32)> {
+        let d1 = self.dir();
+        let d2 = other.dir();
+        if let Some((t, s)) = crate::ray2::line_line_ts(self.a, d1, other.a, d2) {
+            return ((0.0..=1.0).contains(&t) && (0.0..=1.0).contains(&s)).then_some((t, s));
+        }
+
+        // Parallel: they can only meet if they lie on the same line.
+        let length_squared = d1.length_squared();
+        let r = other.a - self.a;
+        if length_squared == 0.0
+            || r.perp_dot(d1).abs() > Line2::PARALLEL_EPSILON * length_squared.sqrt() * r.length()
+        {
+            return None;
+        }
+        let ta = d1.dot(r) / length_squared;
+        let tb = d1.dot(other.b - self.a) / length_squared;
+        let t = ta.min(tb).max(0.0);
+        (t <= ta.max(tb).min(1.0)).then(|| (t, other.closest_t_to_point(self.point_along(t))))
+    }
+
+    /// Intersect the segment with a line.
+    ///
+    /// Returns the `t` along the segment of the crossing point, or [`None`] if it doesn't cross.
+    /// If the segment lies on the line, this is 0.
+    #[cfg(not(target_arch = "spirv"))] // TODO: large Options in rust-gpu
+    pub fn intersect_line(&self, line: &Line2) -> Option<f32> {
+        let da = line.distance(self.a);
+        let db = line.distance(self.b);
+        if da == 0.0 {
+            Some(0.0)
+        } else if da * db > 0.0 || da == db {
+            None
+        } else {
+            Some(da / (da - db))
+        }
+    }
+
+    /// Intersect the segment with the edge of a circle.
+    ///
+    /// Returns the `t` along the segment of the first point where it crosses the edge,
+    /// or [`None`] if it doesn't.
+    /// This is also [`None`] if the segment is entirely inside the circle,
+    /// see [`Self::intersects_circle`].
+    #[cfg(not(target_arch = "spirv"))] // TODO: large Options in rust-gpu
+    pub fn intersect_circle(&self, circle: &crate::Circle) -> Option<f32> {
+        let (t, _normal) = self.to_ray2().intersect_circle(circle)?;
+        (t <= 1.0).then_some(t)
+    }
+
+    /// Returns `true` if any part of the segment is within (or on the edge of) the circle.
+    #[inline]
+    pub fn intersects_circle(&self, circle: &crate::Circle) -> bool {
+        circle.contains(self.closest_point_to_point(circle.center))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::Circle;
+
+    #[test]
+    fn test_segment2_closest() {
+        #![allow(clippy::float_cmp)]
+        let segment = Segment2::new(Vec2::ZERO, Vec2::new(4.0, 0.0));
+        assert_eq!(segment.closest_t_to_point(Vec2::new(1.0, 3.0)), 0.25);
+        assert_eq!(segment.closest_t_to_point(Vec2::new(-1.0, 3.0)), 0.0);
+        assert_eq!(segment.distance_to_point(Vec2::new(7.0, 4.0)), 5.0);
+        assert_eq!(segment.side(Vec2::new(1.0, 3.0), 1e-6), Side::Front);
+        assert_eq!(segment.side(Vec2::new(1.0, -3.0), 1e-6), Side::Back);
+        assert_eq!(segment.side(Vec2::new(9.0, 0.0), 1e-6), Side::On);
+
+        let crossing = Segment2::new(Vec2::new(1.0, -1.0), Vec2::new(1.0, 1.0));
+        assert_eq!(segment.closest_ts(&crossing), (0.25, 0.5));
+        assert_eq!(segment.distance_to_segment(&crossing), 0.0);
+
+        let above = Segment2::new(Vec2::new(2.0, 1.0), Vec2::new(3.0, 5.0));
+        assert_eq!(
+            segment.closest_points(&above),
+            (Vec2::new(2.0, 0.0), Vec2::new(2.0, 1.0))
+        );
+
+        let parallel = Segment2::new(Vec2::new(5.0, 2.0), Vec2::new(6.0, 2.0));
+        assert_eq!(
+            segment.closest_points(&parallel),
+            (Vec2::new(4.0, 0.0), Vec2::new(5.0, 2.0))
+        );
+
+        let point = Segment2::new(Vec2::new(3.0, 2.0), Vec2::new(3.0, 2.0));
+        assert_eq!(segment.distance_to_segment(&point), 2.0);
+        assert_eq!(point.distance_to_segment(&segment), 2.0);
+    }
+
+    #[test]
+    fn test_segment2_intersect() {
+        #![allow(clippy::float_cmp)]
+        let segment = Segment2::new(Vec2::ZERO, Vec2::new(4.0, 0.0));
+
+        let crossing = Segment2::new(Vec2::new(1.0, -1.0), Vec2::new(1.0, 1.0));
+        assert_eq!(segment.intersect_segment(&crossing), Some((0.25, 0.5)));
+        let short = Segment2::new(Vec2::new(1.0, 1.0), Vec2::new(1.0, 3.0));
+        assert_eq!(segment.intersect_segment(&short), None);
+
+        let overlapping = Segment2::new(Vec2::new(6.0, 0.0), Vec2::new(2.0, 0.0));
+        assert_eq!(segment.intersect_segment(&overlapping), Some((0.5, 1.0)));
+        assert_eq!(overlapping.intersect_segment(&segment), Some((0.5, 1.0)));
+        let disjoint = Segment2::new(Vec2::new(5.0, 0.0), Vec2::new(6.0, 0.0));
+        assert_eq!(segment.intersect_segment(&disjoint), None);
+        let parallel = Segment2::new(Vec2::new(0.0, 1.0), Vec2::new(4.0, 1.0));
+        assert_eq!(segment.intersect_segment(&parallel), None);
+
+        let line = Line2::from_normal_point(Vec2::X, Vec2::new(3.0, 0.0));
+        assert_eq!(segment.intersect_line(&line), Some(0.75));
+        assert_eq!(segment.intersect_line(&line.flipped()), Some(0.75));
+        assert_eq!(parallel.intersect_line(&Line2::X), None);
+        assert_eq!(segment.intersect_line(&Line2::X), Some(0.0));
+        assert_eq!(short.intersect_line(&Line2::X), None);
+
+        let circle = Circle::from_center_radius(Vec2::new(2.0, 1.0), 2.0);
+        let t = segment.intersect_circle(&circle).unwrap();
+        assert!((segment.point_along(t).x - (2.0 - 3.0_f32.sqrt())).abs() < 1e-6);
+        assert!(segment.intersects_circle(&circle));
+        let inside = Segment2::new(Vec2::new(2.0, 1.0), Vec2::new(2.5, 1.0));
+        assert_eq!(inside.intersect_circle(&circle), None);
+        assert!(inside.intersects_circle(&circle));
+        let outside = Segment2::new(Vec2::new(5.0, 0.0), Vec2::new(5.0, 4.0));
+        assert_eq!(outside.intersect_circle(&circle), None);
+        assert!(!outside.intersects_circle(&circle));
+    }
+}
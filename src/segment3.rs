@@ -0,0 +1,324 @@
+use crate::Ray3;
+use crate::Vec3;
+use crate::triangle::closest_point_on_triangle;
+
+/// A line segment in 3-dimensional space between the points `a` and `b`.
+///
+/// Any point on the segment can be found through the formula `a + t * (b - a)`,
+/// where `t` is in the range `[0, 1]`.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "speedy", derive(speedy::Writable, speedy::Readable))]
+pub struct Segment3 {
+    /// Start of the segment.
+    pub a: Vec3,
+    /// End of the segment.
+    pub b: Vec3,
+}
+
+impl Segment3 {
+    /// Create a segment between two points.
+    #[inline]
+    pub fn new(a: Vec3, b: Vec3) -> Self {
+        Self { a, b }
+    }
+
+    /// The vector from `a` to `b`.
+    #[inline]
+    pub fn dir(&self) -> Vec3 {
+        self.b - self.a
+    }
+
+    /// The distance between `a` and `b`.
+    #[inline]
+    pub fn length(&self) -> f32 {
+        self.a.distance(self.b)
+    }
+
+    /// The squared distance between `a` and `b`.
+    #[inline]
+    pub fn length_squared(&self) -> f32 {
+        self.a.distance_squared(self.b)
+    }
+
+    /// The point halfway between `a` and `b`.
+    #[inline]
+    pub fn midpoint(&self) -> Vec3 {
+        self.a.midpoint(self.b)
+    }
+
+    /// True if `a == b`.
+    #[inline]
+    pub fn is_point(&self) -> bool {
+        self.a == self.b
+    }
+
+    /// True if every value is finite
+    #[inline]
+    pub fn is_finite(&self) -> bool {
+        self.a.is_finite() && self.b.is_finite()
+    }
+
+    /// The point `a + t * (b - a)`.
+    #[inline]
+    pub fn point_along(&self, t: f32) -> Vec3 {
+        self.a.lerp(self.b, t)
+    }
+
+    /// A ray starting at `a` going towards `b`, with an unnormalized direction,
+    /// so that `t` along the ray and `t` along the segment are the same.
+    #[inline]
+    pub fn to_ray3(&self) -> Ray3 {
+        Ray3::from_origin_dir(self.a, self.dir())
+    }
+
+    /// Returns the `t` in `[0, 1]` of the point on the segment closest to `point`.
+    ///
+    /// Returns 0 if the segment is a point.
+    pub fn closest_t_to_point(&self, point: Vec3) -> f32 {
+        let dir = self.dir();
+        let length_squared = dir.length_squared();
+        if length_squared == 0.0 {
+            0.0
+        } else {
+            (dir.dot(point - self.a) / length_squared).clamp(0.0, 1.0)
+        }
+    }
+
+    /// Returns the point on the segment closest to `point`.
+    #[inline]
+    pub fn closest_point_to_point(&self, point: Vec3) -> Vec3 {
+        self.point_along(self.closest_t_to_point(point))
+    }
+
+    /// The distance between the segment and `point`.
+    #[inline]
+    pub fn distance_to_point(&self, point: Vec3) -> f32 {
+        self.closest_point_to_point(point).distance(point)
+    }
+
+    /// Returns the `t` along both segments of the points where they are closest to each other.
+    ///
+    /// If the segments are parallel and there are many such pairs of points, one of them is returned.
+    /// Segments that are points are handled as well.
+    ///
+    /// From "Real-Time Collision Detection" by Christer Ericson, section 5.1.9.
+    pub fn closest_ts(&self, other: &Self) -> (f32, f32) {
+        let d1 = self.dir();
+        let d2 = other.dir();
+        let r = self.a - other.a;
+        let a = d1.length_squared();
+        let e = d2.length_squared();
+        let f = d2.dot(r);
+
+        if a == 0.0 && e == 0.0 {
+            return (0.0, 0.0);
+        }
+        if a == 0.0 {
+            return (0.0, (f / e).clamp(0.0, 1.0));
+        }
+
+        let c = d1.dot(r);
+        if e == 0.0 {
+            return ((-c / a).clamp(0.0, 1.0), 0.0);
+        }
+
+        let b = d1.dot(d2);
+        let denom = a * e - b * b;
+
+        // For (nearly) parallel segments any `s` works, so pick `s = 0`.
+        let s = if denom > f32::EPSILON * a * e {
+            ((b * f - c * e) / denom).clamp(0.0, 1.0)
+        } else {
+            0.0
+        };
+
+        // The closest point on the other segment to `self.point_along(s)`,
+        // and if that needs clamping, the closest point on `self` to the clamped point.
+        let t = (b * s + f) / e;
+        if t < 0.0 {
+            ((-c / a).clamp(0.0, 1.0), 0.0)
+        } else if 1.0 < t {
+            (((b - c) / a).clamp(0.0, 1.0), 1.0)
+        } else {
+            (s, t)
+        }
+    }
+
+    /// Returns the points on both segments where they are closest to each other.
+    ///
+    /// See [`Self::closest_ts`].
+    pub fn closest_points(&self, other: &Self) -> (Vec3, Vec3) {
+        let (s, t) = self.closest_ts(other);
+        (self.point_along(s), other.point_along(t))
+    }
+
+    /// The distance between the closest points of two segments.
+    pub fn distance_to_segment(&self, other: &Self) -> f32 {
+        let (p, q) = self.closest_points(other);
+        p.distance(q)
+    }
+
+    /// Returns the points on the segment and on the triangle where they are closest to each other.
+    ///
+    /// If the segment crosses the triangle, both points are the crossing point.
+    pub fn closest_points_to_triangle(&self, triangle: [Vec3; 3]) -> (Vec3, Vec3) {
+        let crossing = self.to_ray3().intersect_triangle(triangle, false);
+        if let Some((t, _, _)) = crossing.filter(|&(t, _, _)| t <= 1.0) {
+            let p = self.point_along(t);
+            return (p, p);
+        }
+
+        // Otherwise the closest points involve an end point of the segment or an edge of the triangle.
+        let [a, b, c] = triangle;
+        let candidates = [
+            (self.a, closest_point_on_triangle(self.a, triangle)),
+            (self.b, closest_point_on_triangle(self.b, triangle)),
+            self.closest_points(&Self::new(a, b)),
+            self.closest_points(&Self::new(b, c)),
+            self.closest_points(&Self::new(c, a)),
+        ];
+        candidates
+            .into_iter()
+            .min_by(|(p0, q0), (p1, q1)| {
+                p0.distance_squared(*q0)
+                    .total_cmp(&p1.distance_squared(*q1))
+            })
+            .unwrap_or_default()
+    }
+
+    /// The distance between the segment and a triangle.
+    pub fn distance_to_triangle(&self, triangle: [Vec3; 3]) -> f32 {
+        let (p, q) = self.closest_points_to_triangle(triangle);
+        p.distance(q)
+    }
+}
+
+impl core::ops::Mul<Segment3> for crate::IsoTransform {
+    type Output = Segment3;
+
+    fn mul(self, rhs: Segment3) -> Segment3 {
+        Segment3 {
+            a: self.transform_point3(rhs.a),
+            b: self.transform_point3(rhs.b),
+        }
+    }
+}
+
+impl core::ops::Mul<Segment3> for crate::Conformal3 {
+    type Output = Segment3;
+
+    fn mul(self, rhs: Segment3) -> Segment3 {
+        Segment3 {
+            a: self.transform_point3(rhs.a),
+            b: self.transform_point3(rhs.b),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::Conformal3;
+    use crate::IsoTransform;
+    use crate::Quat;
+
+    #[test]
+    fn test_closest_to_point() {
+        #![allow(clippy::float_cmp)]
+        let segment = Segment3::new(Vec3::ZERO, Vec3::new(2.0, 0.0, 0.0));
+        assert_eq!(segment.closest_t_to_point(Vec3::new(1.0, 5.0, 0.0)), 0.5);
+        assert_eq!(segment.closest_t_to_point(Vec3::new(-1.0, 5.0, 0.0)), 0.0);
+        assert_eq!(segment.distance_to_point(Vec3::new(5.0, 4.0, 0.0)), 5.0);
+
+        let point = Segment3::new(Vec3::ONE, Vec3::ONE);
+        assert_eq!(point.closest_point_to_point(Vec3::ZERO), Vec3::ONE);
+    }
+
+    #[test]
+    fn test_closest_points() {
+        #![allow(clippy::float_cmp)]
+        let s1 = Segment3::new(Vec3::new(-1.0, 0.0, 0.0), Vec3::new(1.0, 0.0, 0.0));
+
+        // Crossing.
+        let s2 = Segment3::new(Vec3::new(0.5, -1.0, 1.0), Vec3::new(0.5, 1.0, 1.0));
+        assert_eq!(
+            s1.closest_points(&s2),
+            (Vec3::new(0.5, 0.0, 0.0), Vec3::new(0.5, 0.0, 1.0))
+        );
+        assert_eq!(s1.distance_to_segment(&s2), 1.0);
+
+        // Clamped at the ends.
+        let s2 = Segment3::new(Vec3::new(3.0, 1.0, 0.0), Vec3::new(3.0, 5.0, 0.0));
+        assert_eq!(
+            s1.closest_points(&s2),
+            (Vec3::new(1.0, 0.0, 0.0), Vec3::new(3.0, 1.0, 0.0))
+        );
+
+        // Parallel and overlapping.
+        let s2 = Segment3::new(Vec3::new(0.0, 2.0, 0.0), Vec3::new(4.0, 2.0, 0.0));
+        let (p, q) = s1.closest_points(&s2);
+        assert_eq!(p.distance(q), 2.0);
+        assert!(p.x >= 0.0 && p.x <= 1.0);
+
+        // Parallel and disjoint.
+        let s2 = Segment3::new(Vec3::new(5.0, 0.0, 0.0), Vec3::new(3.0, 0.0, 0.0));
+        assert_eq!(
+            s1.closest_points(&s2),
+            (Vec3::new(1.0, 0.0, 0.0), Vec3::new(3.0, 0.0, 0.0))
+        );
+
+        // Degenerate.
+        let point = Segment3::new(Vec3::new(0.0, 3.0, 0.0), Vec3::new(0.0, 3.0, 0.0));
+        assert_eq!(s1.distance_to_segment(&point), 3.0);
+        assert_eq!(point.distance_to_segment(&s1), 3.0);
+        assert_eq!(point.distance_to_segment(&point), 0.0);
+    }
+
+    #[test]
+    fn test_distance_to_triangle() {
+        #![allow(clippy::float_cmp)]
+        let triangle = [
+            Vec3::ZERO,
+            Vec3::new(2.0, 0.0, 0.0),
+            Vec3::new(0.0, 2.0, 0.0),
+        ];
+
+        // Crossing the triangle.
+        let segment = Segment3::new(Vec3::new(0.5, 0.5, -1.0), Vec3::new(0.5, 0.5, 1.0));
+        assert_eq!(
+            segment.closest_points_to_triangle(triangle),
+            (Vec3::new(0.5, 0.5, 0.0), Vec3::new(0.5, 0.5, 0.0))
+        );
+
+        // Above the triangle, with the end point closest.
+        let segment = Segment3::new(Vec3::new(0.5, 0.5, 1.0), Vec3::new(0.5, 0.5, 3.0));
+        assert_eq!(segment.distance_to_triangle(triangle), 1.0);
+
+        // Passing by an edge.
+        let segment = Segment3::new(Vec3::new(-1.0, 1.0, -1.0), Vec3::new(-1.0, 1.0, 1.0));
+        assert_eq!(
+            segment.closest_points_to_triangle(triangle),
+            (Vec3::new(-1.0, 1.0, 0.0), Vec3::new(0.0, 1.0, 0.0))
+        );
+    }
+
+    #[test]
+    fn test_transform() {
+        let segment = Segment3::new(Vec3::ZERO, Vec3::X);
+        let iso = IsoTransform::from_rotation_translation(
+            Quat::from_rotation_z(std::f32::consts::FRAC_PI_2),
+            Vec3::ONE,
+        );
+        let transformed = iso * segment;
+        assert!(transformed.a.abs_diff_eq(Vec3::ONE, 1e-6));
+        assert!(transformed.b.abs_diff_eq(Vec3::new(1.0, 2.0, 1.0), 1e-6));
+
+        let conformal = Conformal3::from_scale_rotation_translation(2.0, Quat::IDENTITY, Vec3::Y);
+        let transformed = conformal * segment;
+        assert_eq!(
+            transformed,
+            Segment3::new(Vec3::Y, Vec3::new(2.0, 1.0, 0.0))
+        );
+    }
+}
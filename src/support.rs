@@ -0,0 +1,214 @@
+use crate::BoundingBox;
+use crate::BoundingSphere;
+use crate::Capsule3;
+use crate::Conformal3;
+use crate::IsoTransform;
+use crate::Obb3;
+use crate::Segment3;
+use crate::Vec3;
+
+/// Convex shapes that can tell their furthest point in any direction.
+///
+/// This is all that [`crate::gjk_distance`] and [`crate::epa_penetration`]
+/// need to know about a shape.
+/// Non-convex shapes, such as point clouds, are treated as their convex hull.
+pub trait Support {
+    /// The point of the shape furthest along `dir`, which does not need to be normalized.
+    ///
+    /// If several points are equally far, any of them may be returned.
+    fn support(&self, dir: Vec3) -> Vec3;
+}
+
+impl<S: Support + ?Sized> Support for &S {
+    #[inline]
+    fn support(&self, dir: Vec3) -> Vec3 {
+        (**self).support(dir)
+    }
+}
+
+/// A single point.
+impl Support for Vec3 {
+    #[inline]
+    fn support(&self, _dir: Vec3) -> Vec3 {
+        *self
+    }
+}
+
+impl Support for BoundingBox {
+    #[inline]
+    fn support(&self, dir: Vec3) -> Vec3 {
+        Vec3::select(dir.cmpge(Vec3::ZERO), self.max, self.min)
+    }
+}
+
+impl Support for BoundingSphere {
+    #[inline]
+    fn support(&self, dir: Vec3) -> Vec3 {
+        self.center + dir.normalize_or_zero() * self.radius
+    }
+}
+
+impl Support for Capsule3 {
+    #[inline]
+    fn support(&self, dir: Vec3) -> Vec3 {
+        self.segment().support(dir) + dir.normalize_or_zero() * self.radius
+    }
+}
+
+impl Support for Segment3 {
+    #[inline]
+    fn support(&self, dir: Vec3) -> Vec3 {
+        if dir.dot(self.a) >= dir.dot(self.b) {
+            self.a
+        } else {
+            self.b
+        }
+    }
+}
+
+/// A triangle.
+impl Support for [Vec3; 3] {
+    #[inline]
+    fn support(&self, dir: Vec3) -> Vec3 {
+        self.as_slice().support(dir)
+    }
+}
+
+/// A point cloud, treated as its convex hull.
+///
+/// Returns NaN if there are no points.
+impl Support for [Vec3] {
+    fn support(&self, dir: Vec3) -> Vec3 {
+        self.iter()
+            .copied()
+            .max_by(|a, b| a.dot(dir).total_cmp(&b.dot(dir)))
+            .unwrap_or(Vec3::NAN)
+    }
+}
+
+impl Support for Obb3 {
+    #[inline]
+    fn support(&self, dir: Vec3) -> Vec3 {
+        let local_dir = self.rotation.inverse() * dir;
+        let corner = Vec3::select(local_dir.cmpge(Vec3::ZERO), self.half_size, -self.half_size);
+        self.center + self.rotation * corner
+    }
+}
+
+/// A shape placed by a transform, which is only applied to the support points as they are found.
+///
+/// This avoids transforming every point of shapes such as point clouds up front.
+#[derive(Clone, Copy, Debug)]
+pub struct Transformed<'a, S: ?Sized, T> {
+    /// The shape, in its local space.
+    pub shape: &'a S,
+    /// From the local space of the shape to world space.
+    pub transform: T,
+}
+
+impl<'a, S: ?Sized, T> Transformed<'a, S, T> {
+    /// Place `shape` in world space using `transform`.
+    #[inline]
+    pub fn new(shape: &'a S, transform: T) -> Self {
+        Self { shape, transform }
+    }
+}
+
+impl<S: Support + ?Sized> Support for Transformed<'_, S, IsoTransform> {
+    #[inline]
+    fn support(&self, dir: Vec3) -> Vec3 {
+        let local_dir = self.transform.rotation().inverse() * dir;
+        self.transform
+            .transform_point3(self.shape.support(local_dir))
+    }
+}
+
+/// Since the scale is uniform, it does not change which point is furthest,
+/// except that a negative scale flips the shape through its origin.
+impl<S: Support + ?Sized> Support for Transformed<'_, S, Conformal3> {
+    #[inline]
+    fn support(&self, dir: Vec3) -> Vec3 {
+        let local_dir =
+            self.transform.scale().signum() * (self.transform.rotation().inverse() * dir);
+        self.transform
+            .transform_point3(self.shape.support(local_dir))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::Quat;
+
+    #[test]
+    fn test_support() {
+        #![allow(clippy::float_cmp)]
+        let bb = BoundingBox::from_min_max(Vec3::ZERO, Vec3::ONE);
+        assert_eq!(
+            bb.support(Vec3::new(1.0, -1.0, 0.5)),
+            Vec3::new(1.0, 0.0, 1.0)
+        );
+
+        let sphere = BoundingSphere::from_center_radius(Vec3::ONE, 2.0);
+        assert_eq!(
+            sphere.support(Vec3::new(0.0, 0.0, -5.0)),
+            Vec3::new(1.0, 1.0, -1.0)
+        );
+
+        let capsule = Capsule3::new(Vec3::ZERO, Vec3::Y, 0.5);
+        assert_eq!(capsule.support(Vec3::Y), Vec3::new(0.0, 1.5, 0.0));
+        assert_eq!(capsule.support(Vec3::X).x, 0.5);
+
+        let triangle = [Vec3::ZERO, Vec3::X, Vec3::Y];
+        assert_eq!(triangle.support(Vec3::new(1.0, 2.0, 0.0)), Vec3::Y);
+
+        let points: Vec<Vec3> = vec![Vec3::ZERO, Vec3::splat(2.0), Vec3::NEG_ONE];
+        assert_eq!(points.as_slice().support(Vec3::ONE), Vec3::splat(2.0));
+        assert!([].as_slice().support(Vec3::ONE).is_nan());
+
+        let obb = Obb3::from_center_half_size_rotation(
+            Vec3::ZERO,
+            Vec3::new(2.0, 1.0, 1.0),
+            Quat::from_rotation_z(std::f32::consts::FRAC_PI_2),
+        );
+        assert!(
+            obb.support(Vec3::ONE)
+                .abs_diff_eq(Vec3::new(1.0, 2.0, 1.0), 1e-6)
+        );
+
+        // Placed shapes match shapes transformed up front.
+        let iso = IsoTransform::from_rotation_translation(
+            Quat::from_rotation_y(1.0),
+            Vec3::new(1.0, 2.0, 3.0),
+        );
+        let placed = Transformed::new(points.as_slice(), iso);
+        let moved: Vec<Vec3> = points.iter().map(|&p| iso.transform_point3(p)).collect();
+        for dir in [Vec3::X, Vec3::NEG_Y, Vec3::new(1.0, -2.0, 0.5)] {
+            assert!(
+                placed
+                    .support(dir)
+                    .abs_diff_eq(moved.as_slice().support(dir), 1e-5)
+            );
+        }
+
+        let conformal =
+            Conformal3::from_scale_rotation_translation(3.0, Quat::from_rotation_x(0.5), Vec3::X);
+        let placed = Transformed::new(&capsule, conformal);
+        let moved = conformal * capsule;
+        for dir in [Vec3::X, Vec3::NEG_Y, Vec3::new(1.0, -2.0, 0.5)] {
+            assert!(placed.support(dir).abs_diff_eq(moved.support(dir), 1e-5));
+        }
+
+        // A negative scale mirrors the shape through the origin.
+        let mirror = Conformal3::from_scale_rotation_translation(-1.0, Quat::IDENTITY, Vec3::ZERO);
+        let placed = Transformed::new(&bb, mirror);
+        assert_eq!(placed.support(Vec3::X).x, 0.0);
+        assert_eq!(placed.support(Vec3::NEG_ONE), Vec3::NEG_ONE);
+        let placed = Transformed::new(&capsule, mirror);
+        let moved = mirror * capsule;
+        assert_eq!(moved.radius, 0.5);
+        for dir in [Vec3::X, Vec3::NEG_Y, Vec3::new(1.0, -2.0, 0.5)] {
+            assert!(placed.support(dir).abs_diff_eq(moved.support(dir), 1e-5));
+        }
+    }
+}
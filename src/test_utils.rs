@@ -0,0 +1,25 @@
+use crate::BoundingBox;
+use crate::Vec3;
+
+/// A small deterministic random number generator (xorshift), seeded with a non-zero value.
+pub(crate) struct Rng(pub(crate) u64);
+
+impl Rng {
+    /// In `[0, 1)`.
+    pub(crate) fn next_f32(&mut self) -> f32 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        (self.0 >> 40) as f32 / (1 << 24) as f32
+    }
+
+    /// Each component in `[0, 1)`.
+    pub(crate) fn next_vec3(&mut self) -> Vec3 {
+        Vec3::new(self.next_f32(), self.next_f32(), self.next_f32())
+    }
+
+    /// A box of size up to 5 somewhere within 100 of the origin, along the positive axes.
+    pub(crate) fn next_box(&mut self) -> BoundingBox {
+        BoundingBox::from_center_size(100.0 * self.next_vec3(), 5.0 * self.next_vec3())
+    }
+}
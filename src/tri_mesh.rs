@@ -0,0 +1,242 @@
+use crate::BoundingBox;
+use crate::Bvh;
+use crate::MeshGen;
+use crate::Ray3;
+use crate::RayHit;
+use crate::Raycast;
+use crate::Vec3;
+use crate::triangle::closest_point_on_triangle;
+
+/// The rays used by [`TriMesh::contains`].
+///
+/// Arbitrary directions, none of them close to an axis or to a diagonal of a cube,
+/// so that they don't line up with the edges and corners of axis-aligned meshes.
+const CONTAINS_DIRS: [Vec3; 3] = [
+    Vec3::new(0.6124, 0.2588, -0.7470),
+    Vec3::new(-0.3197, 0.8660, -0.3844),
+    Vec3::new(0.2179, -0.4242, 0.8791),
+];
+
+/// A triangle mesh for spatial queries, borrowing its positions and triangle indices.
+///
+/// The triangles are indexed in the same layout as [`MeshGen`]: three indices per triangle,
+/// with the front side being where the corners are in counter-clockwise order.
+///
+/// A [`Bvh`] over the triangles is built on creation, so queries stay fast on large meshes.
+#[derive(Clone, Debug)]
+pub struct TriMesh<'a> {
+    positions: &'a [Vec3],
+    indices: &'a [u32],
+    bvh: Bvh,
+}
+
+impl<'a> TriMesh<'a> {
+    /// Create a mesh from positions and triangle indices into them.
+    ///
+    /// # Panics
+    ///
+    /// If the number of indices is not a multiple of three,
+    /// or if any index is out of bounds.
+    pub fn new(positions: &'a [Vec3], indices: &'a [u32]) -> Self {
+        assert!(
+            indices.len().is_multiple_of(3),
+            "Expected three indices per triangle, got {} indices",
+            indices.len()
+        );
+
+        let boxes: Vec<BoundingBox> = indices
+            .chunks_exact(3)
+            .map(|triangle| {
+                BoundingBox::from_points(triangle.iter().map(|&i| positions[i as usize]))
+            })
+            .collect();
+
+        Self {
+            positions,
+            indices,
+            bvh: Bvh::build(&boxes),
+        }
+    }
+
+    /// Create a mesh from the positions and indices of a [`MeshGen`].
+    pub fn from_mesh_gen(mesh: &'a MeshGen) -> Self {
+        Self::new(&mesh.positions, &mesh.indices)
+    }
+
+    /// The positions the mesh was created from.
+    #[inline]
+    pub fn positions(&self) -> &'a [Vec3] {
+        self.positions
+    }
+
+    /// The triangle indices the mesh was created from.
+    #[inline]
+    pub fn indices(&self) -> &'a [u32] {
+        self.indices
+    }
+
+    /// The number of triangles.
+    #[inline]
+    pub fn num_triangles(&self) -> usize {
+        self.indices.len() / 3
+    }
+
+    /// The corners of a triangle.
+    #[inline]
+    pub fn triangle(&self, triangle: usize) -> [Vec3; 3] {
+        let i = &self.indices[3 * triangle..3 * triangle + 3];
+        [
+            self.positions[i[0] as usize],
+            self.positions[i[1] as usize],
+            self.positions[i[2] as usize],
+        ]
+    }
+
+    /// The bounds of all triangles, or [`BoundingBox::nothing`] if there are none.
+    #[inline]
+    pub fn bounding_box(&self) -> BoundingBox {
+        self.bvh.bounds()
+    }
+
+    /// Find the first triangle hit by the ray, from either side.
+    ///
+    /// Returns the index of the triangle and the hit, considering only hits in the range `[0, max_t]`.
+    /// The normal of the hit is the normal of the front side of the triangle.
+    pub fn raycast_triangle(&self, ray: &Ray3, max_t: f32) -> Option<(usize, RayHit)> {
+        let (triangle, t) = self.bvh.nearest_ray_hit(ray, max_t, |triangle| {
+            ray.intersect_triangle(self.triangle(triangle), false)
+                .map(|(t, _, _)| t)
+        })?;
+        let [a, b, c] = self.triangle(triangle);
+        let normal = (b - a).cross(c - a).normalize();
+        Some((triangle, RayHit::from_ray_t_normal(ray, t, normal)))
+    }
+
+    /// Find the point on the surface of the mesh closest to `point`.
+    ///
+    /// Returns the index of the triangle the closest point is on, and the closest point,
+    /// or [`None`] if the mesh has no triangles.
+    pub fn closest_point(&self, point: Vec3) -> Option<(usize, Vec3)> {
+        let (triangle, _) = self
+            .bvh
+            .nearest_to_point(point, f32::INFINITY, |triangle| {
+                Some(closest_point_on_triangle(point, self.triangle(triangle)).distance(point))
+            })?;
+        Some((
+            triangle,
+            closest_point_on_triangle(point, self.triangle(triangle)),
+        ))
+    }
+
+    /// Returns `true` if the point is inside the mesh.
+    ///
+    /// Only meaningful for closed (watertight) meshes.
+    /// Counts how many times rays from the point cross the surface,
+    /// taking the majority vote of a few rays to be robust against hitting edges.
+    pub fn contains(&self, point: Vec3) -> bool {
+        if !self.bounding_box().contains(point) {
+            return false;
+        }
+
+        let inside_votes = CONTAINS_DIRS
+            .iter()
+            .filter(|&&dir| self.num_crossings(&Ray3::from_origin_dir(point, dir)) % 2 == 1)
+            .count();
+
+        2 * inside_votes > CONTAINS_DIRS.len()
+    }
+
+    /// How many triangles the ray crosses.
+    fn num_crossings(&self, ray: &Ray3) -> usize {
+        let mut crossings = 0;
+        self.bvh.for_each_ray_hit(ray, f32::INFINITY, |triangle| {
+            if ray
+                .intersect_triangle(self.triangle(triangle), false)
+                .is_some()
+            {
+                crossings += 1;
+            }
+        });
+        crossings
+    }
+}
+
+impl Raycast for TriMesh<'_> {
+    fn raycast(&self, ray: &Ray3, max_t: f32) -> Option<RayHit> {
+        self.raycast_triangle(ray, max_t).map(|(_, hit)| hit)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::IsoTransform;
+
+    #[test]
+    fn test_tri_mesh() {
+        let mut mesh_gen = MeshGen::new();
+        mesh_gen.push_cube(Vec3::ONE, IsoTransform::IDENTITY);
+        mesh_gen.push_sphere(
+            1.0,
+            16,
+            16,
+            IsoTransform::from_translation(Vec3::new(5.0, 0.0, 0.0)),
+        );
+        let mesh = TriMesh::from_mesh_gen(&mesh_gen);
+
+        let bb = mesh.bounding_box();
+        assert!(bb.min.abs_diff_eq(Vec3::splat(-1.0), 1e-6));
+        assert!(bb.max.abs_diff_eq(Vec3::new(6.0, 1.0, 1.0), 1e-6));
+
+        // Hits the cube first, with an outwards normal.
+        let ray = Ray3::from_origin_dir(Vec3::new(-5.0, 0.25, 0.5), Vec3::X);
+        let hit = mesh.raycast(&ray, 100.0).unwrap();
+        assert!((hit.t - 4.0).abs() < 1e-5);
+        assert!(hit.normal.abs_diff_eq(-Vec3::X, 1e-6));
+        assert_eq!(mesh.raycast(&ray, 3.0), None);
+
+        // From the other side, hits the sphere first.
+        let ray = Ray3::from_origin_dir(Vec3::new(10.0, 0.0, 0.0), -Vec3::X);
+        let (triangle, hit) = mesh.raycast_triangle(&ray, 100.0).unwrap();
+        assert!((hit.t - 4.0).abs() < 0.01);
+        assert!(hit.normal.dot(Vec3::X) > 0.9);
+        assert!(3 * triangle >= 36, "The sphere comes after the cube");
+
+        let (_, closest) = mesh.closest_point(Vec3::new(0.5, 3.0, 0.25)).unwrap();
+        assert!(closest.abs_diff_eq(Vec3::new(0.5, 1.0, 0.25), 1e-6));
+        let (_, closest) = mesh.closest_point(Vec3::new(0.5, 0.8, 0.25)).unwrap();
+        assert!(closest.abs_diff_eq(Vec3::new(0.5, 1.0, 0.25), 1e-6));
+
+        assert!(mesh.contains(Vec3::ZERO));
+        assert!(mesh.contains(Vec3::new(0.9, -0.9, 0.9)));
+        assert!(mesh.contains(Vec3::new(5.0, 0.1, 0.2)));
+        assert!(!mesh.contains(Vec3::new(3.0, 0.0, 0.0)));
+        assert!(!mesh.contains(Vec3::new(1.1, 0.0, 0.0)));
+
+        // Every ray from a point on a diagonal of a cube should cross it once,
+        // without going through its corners.
+        let mut cube_gen = MeshGen::new();
+        cube_gen.push_cube(Vec3::ONE, IsoTransform::IDENTITY);
+        let cube = TriMesh::from_mesh_gen(&cube_gen);
+        for diagonal in [
+            Vec3::new(1.0, 1.0, 1.0),
+            Vec3::new(-1.0, 1.0, 1.0),
+            Vec3::new(1.0, -1.0, 1.0),
+            Vec3::new(1.0, 1.0, -1.0),
+        ] {
+            for i in -9..=9 {
+                let point = diagonal * (i as f32 * 0.1);
+                assert!(cube.contains(point), "{point} should be inside");
+                for dir in CONTAINS_DIRS {
+                    let ray = Ray3::from_origin_dir(point, dir);
+                    assert_eq!(cube.num_crossings(&ray), 1, "From {point} along {dir}");
+                }
+            }
+        }
+
+        let empty = TriMesh::new(&[], &[]);
+        assert!(empty.bounding_box().is_nothing());
+        assert_eq!(empty.closest_point(Vec3::ZERO), None);
+        assert!(!empty.contains(Vec3::ZERO));
+    }
+}
@@ -0,0 +1,87 @@
+use crate::Vec3;
+
+/// The point on the triangle closest to `p`.
+pub(crate) fn closest_point_on_triangle(p: Vec3, triangle: [Vec3; 3]) -> Vec3 {
+    let [u, v, w] = closest_barycentric_on_triangle(p, triangle);
+    let [a, b, c] = triangle;
+    u * a + v * b + w * c
+}
+
+/// The barycentric coordinates of the point on the triangle closest to `p`.
+///
+/// From "Real-Time Collision Detection" by Christer Ericson, section 5.1.5.
+pub(crate) fn closest_barycentric_on_triangle(p: Vec3, [a, b, c]: [Vec3; 3]) -> [f32; 3] {
+    let ab = b - a;
+    let ac = c - a;
+
+    // Vertex region outside `a`.
+    let ap = p - a;
+    let d1 = ab.dot(ap);
+    let d2 = ac.dot(ap);
+    if d1 <= 0.0 && d2 <= 0.0 {
+        return [1.0, 0.0, 0.0];
+    }
+
+    // Vertex region outside `b`.
+    let bp = p - b;
+    let d3 = ab.dot(bp);
+    let d4 = ac.dot(bp);
+    if d3 >= 0.0 && d4 <= d3 {
+        return [0.0, 1.0, 0.0];
+    }
+
+    // Edge region of `ab`.
+    let vc = d1 * d4 - d3 * d2;
+    if vc <= 0.0 && d1 >= 0.0 && d3 <= 0.0 {
+        let v = d1 / (d1 - d3);
+        return [1.0 - v, v, 0.0];
+    }
+
+    // Vertex region outside `c`.
+    let cp = p - c;
+    let d5 = ab.dot(cp);
+    let d6 = ac.dot(cp);
+    if d6 >= 0.0 && d5 <= d6 {
+        return [0.0, 0.0, 1.0];
+    }
+
+    // Edge region of `ac`.
+    let vb = d5 * d2 - d1 * d6;
+    if vb <= 0.0 && d2 >= 0.0 && d6 <= 0.0 {
+        let w = d2 / (d2 - d6);
+        return [1.0 - w, 0.0, w];
+    }
+
+    // Edge region of `bc`.
+    let va = d3 * d6 - d5 * d4;
+    if va <= 0.0 && (d4 - d3) >= 0.0 && (d5 - d6) >= 0.0 {
+        let w = (d4 - d3) / ((d4 - d3) + (d5 - d6));
+        return [0.0, 1.0 - w, w];
+    }
+
+    // Inside the face.
+    let denom = (va + vb + vc).recip();
+    let v = vb * denom;
+    let w = vc * denom;
+    [1.0 - v - w, v, w]
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_closest_point_on_triangle() {
+        let triangle = [Vec3::ZERO, Vec3::X, Vec3::Y];
+        let closest = |p| closest_point_on_triangle(p, triangle);
+        assert_eq!(
+            closest(Vec3::new(0.25, 0.25, 1.0)),
+            Vec3::new(0.25, 0.25, 0.0)
+        );
+        assert_eq!(closest(Vec3::new(-1.0, -1.0, 0.0)), Vec3::ZERO);
+        assert_eq!(closest(Vec3::new(2.0, -1.0, 0.0)), Vec3::X);
+        assert_eq!(closest(Vec3::new(0.5, -1.0, 3.0)), Vec3::new(0.5, 0.0, 0.0));
+        assert_eq!(closest(Vec3::new(-1.0, 0.5, 0.0)), Vec3::new(0.0, 0.5, 0.0));
+        assert_eq!(closest(Vec3::new(1.0, 1.0, 0.0)), Vec3::new(0.5, 0.5, 0.0));
+    }
+}